@@ -17,23 +17,84 @@ limitations under the License.
 // Bugzilla API documentation:
 // https://bugzilla.redhat.com/docs/en/html/api/core/v1/general.html
 
-use crate::bug_model::{Bug, Response};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::OnceCell;
+
+use crate::bug_model::{
+    Attachment, AttachmentsResponse, Bug, BugPage, BugzillaError, Comment, CommentsResponse,
+    HistoryEntry, HistoryResponse, Response, UpdateResponse, User,
+};
+use crate::bug_update::BugUpdate;
 use crate::errors::BugzillaQueryError;
 
 /// Configuration and credentials to access a Bugzilla instance.
+///
+/// Each `BzInstance` holds a single HTTP client, which keeps its connections open
+/// between requests. Create one instance and reuse it for all your queries,
+/// rather than creating a new instance for each bug. That way, only the first
+/// request pays for setting up the connection and the TLS handshake.
+/// To share the connection pool with other parts of your application,
+/// pass your own client to [`BzInstance::with_client`].
+///
+/// `BzInstance` is `Send` and `Sync`, so you can share it between threads and tasks
+/// behind a reference or an `Arc`. Cloning an instance is cheap: the clone shares
+/// the connection pool and keeps the session token from any earlier login,
+/// but you can change its options, such as the pagination, without affecting the original.
+#[derive(Clone)]
 pub struct BzInstance {
     pub host: String,
+    /// Fallback hosts that serve read requests if `host` is unavailable.
+    pub mirrors: Vec<String>,
     pub auth: Auth,
     pub pagination: Pagination,
     pub included_fields: Vec<String>,
+    /// How many times to retry a request that failed with a transient error.
+    pub retries: u32,
+    /// The delay before the first retry. Each following retry doubles the delay.
+    pub retry_delay: Duration,
+    /// The longest delay before a retry, even if the server requests a longer one.
+    pub max_retry_delay: Duration,
+    /// The time limit for each request, including connecting to the host.
+    pub timeout: Duration,
+    /// Extra HTTP headers as name and value pairs, which every request sends.
+    pub headers: Vec<(String, String)>,
+    /// The maximum number of bug IDs in a single request. Longer lists of IDs
+    /// are split into several requests.
+    pub max_ids_per_request: usize,
     client: reqwest::Client,
+    /// Extra root certificates that the client trusts, in addition to the system trust store.
+    root_certificates: Vec<reqwest::Certificate>,
+    /// Skip verifying TLS certificates. Only available with the `danger-accept-invalid-certs` feature.
+    accept_invalid_certs: bool,
+    /// The session token from logging in with `Auth::Login`.
+    token: OnceCell<String>,
 }
 
 /// The authentication method that the crate uses when contacting Bugzilla.
+#[derive(Clone)]
 pub enum Auth {
     Anonymous,
     ApiKey(String),
-    Basic { user: String, password: String },
+    Basic {
+        user: String,
+        password: String,
+    },
+    /// Log in with the user name and password, and authenticate
+    /// the following requests with the session token from Bugzilla.
+    /// The login happens before the first request, and only once.
+    Login {
+        user: String,
+        password: String,
+    },
 }
 
 // We could set a default enum variant and derive, but that raises the MSRV to 1.62.
@@ -48,10 +109,15 @@ impl Default for Auth {
 /// * `Default`: Use the default settings of this instance, which sets an arbitrary limit on the number of bugs.
 /// * `Limit`: Use this upper limit instead.
 /// * `Unlimited`: Set the limit to 0, which disables the upper limit and returns all matching bugs.
+/// * `Page`: Use this upper limit, and skip the first `offset` matching bugs.
+///   Together with `total_matches`, this enables paging through the results manually.
+///   `BugPage::next_page` computes the pagination for the following page.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Pagination {
     Default,
     Limit(u32),
     Unlimited,
+    Page { limit: u32, offset: u32 },
 }
 
 // We could set a default enum variant and derive, but that raises the MSRV to 1.62.
@@ -61,55 +127,406 @@ impl Default for Pagination {
     }
 }
 
+/// The number of bugs per request when downloading all pages of results,
+/// unless `Pagination::Limit` sets a different page size.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
 impl Pagination {
+    /// The number of bugs to request per page when downloading all pages of results.
+    fn page_size(&self) -> u32 {
+        match self {
+            Pagination::Limit(n) | Pagination::Page { limit: n, .. } if *n > 0 => *n,
+            _ => DEFAULT_PAGE_SIZE,
+        }
+    }
+
     /// Format the `Pagination` variant as a URL query fragment, such as `?limit=20`.
     fn url_fragment(&self) -> String {
         match self {
             Pagination::Default => String::new(),
             Pagination::Limit(n) => format!("&limit={n}"),
             Pagination::Unlimited => "&limit=0".to_string(),
+            Pagination::Page { limit, offset } => format!("&limit={limit}&offset={offset}"),
         }
     }
 }
 
+/// The response from Bugzilla to a successful login.
+#[derive(Deserialize)]
+struct LoginResponse {
+    token: String,
+}
+
 /// The method of the request to Bugzilla. Either request specific IDs,
-/// or use a free-form Bugzilla search query as-is.
+/// request a bug by its alias, or use a free-form Bugzilla search query as-is.
 enum Method<'a> {
-    Ids(&'a [&'a str]),
+    Ids(Vec<&'a str>),
+    Alias(&'a str),
     Search(&'a str),
 }
 
-impl<'a> Method<'a> {
+impl Method<'_> {
     fn url_fragment(&self) -> String {
         match self {
             Self::Ids(ids) => format!("id={}", ids.join(",")),
+            Self::Alias(alias) => format!("alias={alias}"),
             Self::Search(query) => (*query).to_string(),
         }
     }
 }
 
+/// The maximum number of bug IDs in a single request, unless configured otherwise.
+const DEFAULT_MAX_IDS_PER_REQUEST: usize = 100;
+
+/// The time limit for each request, unless configured otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The delay before the first retry of a failed request, unless configured otherwise.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The longest delay before a retry, unless configured otherwise.
+const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Decide if a request failed in a way that might fix itself, such as a server error,
+/// rate limiting, or a connection failure. Other client errors are never transient.
+fn is_transient(result: &Result<reqwest::Response, reqwest::Error>) -> bool {
+    match result {
+        Ok(response) => {
+            response.status().is_server_error()
+                || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        Err(error) => error.is_connect() || error.is_timeout(),
+    }
+}
+
+/// The delay that the server requested in the `Retry-After` header, if any.
+/// Only supports the number of seconds, not the HTTP date format.
+fn retry_after(result: &Result<reqwest::Response, reqwest::Error>) -> Option<Duration> {
+    let header = result
+        .as_ref()
+        .ok()?
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?;
+    let seconds = header.to_str().ok()?.trim().parse().ok()?;
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// The exponential delay before the retry that follows the specified number of retries,
+/// with up to 50% of random jitter so that concurrent clients don't retry in sync.
+fn backoff(base: Duration, retries: u32) -> Duration {
+    let delay = base.saturating_mul(2_u32.saturating_pow(retries));
+    let max_jitter = u64::try_from(delay.as_millis() / 2).unwrap_or(u64::MAX);
+    // The standard library seeds every `RandomState` randomly, which is enough for jitter.
+    let random = RandomState::new().build_hasher().finish();
+
+    delay.saturating_add(Duration::from_millis(random % max_jitter.saturating_add(1)))
+}
+
+/// Deserialize the JSON body of a response from Bugzilla.
+///
+/// If the response doesn't match the expected type, try to interpret it
+/// as an error report from Bugzilla, and return it as `BugzillaQueryError::Api`.
+/// Bugzilla sends error reports with various HTTP status codes, including 200.
+async fn parse_response<T: DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, BugzillaQueryError> {
+    // Only report the HTTP status if the body doesn't explain the failure.
+    let status_error = response.error_for_status_ref().err();
+    let body = response.text().await?;
+
+    match serde_json::from_str::<T>(&body) {
+        Ok(parsed) => Ok(parsed),
+        Err(parse_error) => match serde_json::from_str::<BugzillaError>(&body) {
+            Ok(error) if error.error => Err(BugzillaQueryError::Api {
+                code: error.code,
+                message: error.message,
+            }),
+            _ => Err(status_error.map_or_else(|| parse_error.into(), Into::into)),
+        },
+    }
+}
+
+/// Check that the host is an absolute `http` or `https` URL, and remove any trailing slashes,
+/// so that appending the REST paths to it produces valid URLs.
+fn normalize_host(host: String) -> Result<String, BugzillaQueryError> {
+    match reqwest::Url::parse(&host) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {
+            Ok(host.trim_end_matches('/').to_string())
+        }
+        _ => Err(BugzillaQueryError::InvalidHost(host)),
+    }
+}
+
+/// The bug fields that Bugzilla can include in a response: the fields of `Bug`,
+/// the extra fields that Bugzilla only returns on request, and the special field groups.
+const KNOWN_FIELDS: &[&str] = &[
+    "_default",
+    "_all",
+    "_extra",
+    "_custom",
+    "actual_time",
+    "alias",
+    "assigned_to",
+    "attachments",
+    "blocks",
+    "cc",
+    "classification",
+    "comment_count",
+    "comments",
+    "component",
+    "creation_time",
+    "creator",
+    "deadline",
+    "depends_on",
+    "dependent_products",
+    "description",
+    "docs_contact",
+    "dupe_of",
+    "duplicates",
+    "estimated_time",
+    "flags",
+    "groups",
+    "history",
+    "id",
+    "is_cc_accessible",
+    "is_confirmed",
+    "is_creator_accessible",
+    "is_open",
+    "keywords",
+    "last_change_time",
+    "op_sys",
+    "platform",
+    "priority",
+    "product",
+    "qa_contact",
+    "remaining_time",
+    "resolution",
+    "see_also",
+    "severity",
+    "status",
+    "summary",
+    "tags",
+    "target_milestone",
+    "target_release",
+    "update_token",
+    "url",
+    "version",
+    "votes",
+    "whiteboard",
+    "work_time",
+];
+
+/// Check if Bugzilla recognizes the field name in `include_fields`.
+/// Custom fields and subfields, such as `flags.name`, count by their prefix.
+fn is_known_field(field: &str) -> bool {
+    let field = field.split('.').next().unwrap_or(field);
+    field.starts_with("cf_") || KNOWN_FIELDS.contains(&field)
+}
+
+/// Decide if a page of results is the last one, based on the number of bugs in the page
+/// and the number of bugs downloaded so far, including this page.
+///
+/// The results end with an empty page, or when the number of downloaded bugs
+/// reaches `total_matches`. If the response doesn't report `total_matches`,
+/// they also end with the first page that's smaller than the page size.
+fn is_last_page(
+    page_size: usize,
+    downloaded: usize,
+    total_matches: Option<u32>,
+    limit: u32,
+) -> bool {
+    let finished = match total_matches {
+        Some(total) => downloaded >= total as usize,
+        None => page_size < limit as usize,
+    };
+
+    page_size == 0 || finished
+}
+
+/// An asynchronous iterator over the bugs that match a search query.
+/// It downloads the next page of results whenever it runs out of bugs.
+///
+/// Create it with `BzInstance::search_iter`, and call `next` in a loop:
+///
+/// ```no_run
+/// # async fn example(bugzilla: bugzilla_query::BzInstance) {
+/// let mut bugs = bugzilla.search_iter("component=rust&product=Fedora");
+///
+/// while let Some(bug) = bugs.next().await {
+///     println!("{}", bug.unwrap().summary);
+/// }
+/// # }
+/// ```
+pub struct SearchIter<'a> {
+    instance: &'a BzInstance,
+    query: String,
+    limit: u32,
+    downloaded: usize,
+    page: std::vec::IntoIter<Bug>,
+    finished: bool,
+}
+
+impl SearchIter<'_> {
+    /// Return the next matching bug, or `None` after the last one.
+    ///
+    /// If a request fails, return the error, and then stop the iteration.
+    pub async fn next(&mut self) -> Option<Result<Bug, BugzillaQueryError>> {
+        loop {
+            if let Some(bug) = self.page.next() {
+                return Some(Ok(bug));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            let method = Method::Search(&self.query);
+            let path = self
+                .instance
+                .page_path(&method, self.limit, self.downloaded);
+
+            match self.instance.get::<Response>(&path).await {
+                Ok(response) => {
+                    log::debug!("{response:#?}");
+
+                    let page_size = response.bugs.len();
+                    self.downloaded += page_size;
+                    self.finished = is_last_page(
+                        page_size,
+                        self.downloaded,
+                        response.total_matches,
+                        self.limit,
+                    );
+                    self.page = response.bugs.into_iter();
+                }
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
 impl BzInstance {
     /// Create a new `BzInstance` struct using a host URL, with default values
     /// for all options.
+    ///
+    /// The host must be an absolute `http` or `https` URL, such as `https://bugzilla.redhat.com`.
+    /// A trailing slash is removed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the host isn't a valid `http` or `https` URL.
     pub fn at(host: String) -> Result<Self, BugzillaQueryError> {
         // TODO: This function takes host as a String, even though client is happy with &str.
         // The String is only used in the host struct attribute.
 
-        let client = reqwest::Client::new();
+        Self::with_client(host, reqwest::Client::new())
+    }
 
+    /// Create a new `BzInstance` struct using a host URL and an HTTP client
+    /// that you have already configured, with default values for all other options.
+    ///
+    /// Use this to set up proxies, custom root certificates, or a connection pool
+    /// that you share with the rest of your application.
+    /// The options of this `BzInstance`, such as the timeout and the extra headers,
+    /// still apply to each request on top of the client configuration.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the host isn't a valid `http` or `https` URL.
+    pub fn with_client(host: String, client: reqwest::Client) -> Result<Self, BugzillaQueryError> {
         Ok(BzInstance {
-            host,
+            host: normalize_host(host)?,
+            mirrors: Vec::new(),
             client,
             included_fields: vec!["_default".to_string()],
             auth: Auth::default(),
             pagination: Pagination::default(),
+            retries: 0,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            timeout: DEFAULT_TIMEOUT,
+            headers: Vec::new(),
+            max_ids_per_request: DEFAULT_MAX_IDS_PER_REQUEST,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            token: OnceCell::new(),
         })
     }
 
+    /// Create a new `BzInstance` struct using a primary host URL and a list of fallback mirrors,
+    /// with default values for all options.
+    ///
+    /// Read requests try the hosts in order, and move on to the next host
+    /// if the current one fails to connect or responds with a server error.
+    /// The login with `Auth::Login` creates a session on the server, so it only goes
+    /// to the primary host, and the mirrors must accept the session token from the primary host.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the hosts isn't a valid `http` or `https` URL.
+    pub fn with_mirrors(
+        primary: String,
+        fallbacks: Vec<String>,
+    ) -> Result<Self, BugzillaQueryError> {
+        let mut instance = Self::at(primary)?;
+        instance.mirrors = fallbacks
+            .into_iter()
+            .map(normalize_host)
+            .collect::<Result<_, _>>()?;
+        Ok(instance)
+    }
+
+    /// Trust an additional root certificate in the PEM format, such as the certificate
+    /// of a corporate CA that signed the certificate of your internal Bugzilla.
+    ///
+    /// This rebuilds the HTTP client, and replaces any client that you set with `with_client`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the certificate is invalid, or if the HTTP client can't be initialized.
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Result<Self, BugzillaQueryError> {
+        let certificate = reqwest::Certificate::from_pem(pem).map_err(BugzillaQueryError::Tls)?;
+        self.root_certificates.push(certificate);
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Accept any TLS certificate, including self-signed and expired ones.
+    ///
+    /// This makes the connection vulnerable to man-in-the-middle attacks.
+    /// Prefer `add_root_certificate` whenever you can.
+    /// This rebuilds the HTTP client, and replaces any client that you set with `with_client`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the HTTP client can't be initialized.
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Result<Self, BugzillaQueryError> {
+        self.accept_invalid_certs = accept;
+        self.rebuild_client()?;
+        Ok(self)
+    }
+
+    /// Replace the HTTP client with a new one that uses the configured TLS settings.
+    fn rebuild_client(&mut self) -> Result<(), BugzillaQueryError> {
+        let builder = self.root_certificates.iter().fold(
+            reqwest::Client::builder().danger_accept_invalid_certs(self.accept_invalid_certs),
+            |builder, certificate| builder.add_root_certificate(certificate.clone()),
+        );
+        self.client = builder.build().map_err(BugzillaQueryError::Tls)?;
+        Ok(())
+    }
+
     /// Set the authentication method of this `BzInstance`.
     #[must_use]
     pub fn authenticate(mut self, auth: Auth) -> Self {
         self.auth = auth;
+        // Forget the session token from any previous login.
+        self.token = OnceCell::new();
         self
     }
 
@@ -120,17 +537,115 @@ impl BzInstance {
         self
     }
 
+    /// Retry requests that fail with a server error, with rate limiting (429),
+    /// or with a connection failure, up to the specified number of times.
+    /// This includes the login with `Auth::Login`, but not `update_bug`.
+    /// Requests that fail with another client error, such as 404, aren't retried,
+    /// and neither are responses that fail to deserialize.
+    ///
+    /// The delay between retries grows exponentially from `retry_delay`, with random jitter.
+    /// If the server sends the `Retry-After` header, the delay follows the header instead,
+    /// unless the header asks for more than `max_retry_delay`.
+    #[must_use]
+    pub fn with_retries(mut self, max: u32) -> Self {
+        self.retries = max;
+        self
+    }
+
+    /// Set the delay before the first retry of a failed request. The default is 500 ms.
+    #[must_use]
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Set the longest delay before a retry. The default is 30 seconds.
+    ///
+    /// If the `Retry-After` header asks for a longer delay, the retry uses
+    /// the exponential backoff instead. The backoff never exceeds this limit either.
+    #[must_use]
+    pub fn max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = delay;
+        self
+    }
+
+    /// Set the time limit for each request, including connecting to the host.
+    /// The default is 30 seconds.
+    ///
+    /// A request that exceeds the limit fails with `BugzillaQueryError::Timeout`.
+    #[must_use]
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = duration;
+        self
+    }
+
+    /// Set the `User-Agent` header that every request sends, replacing any previous value.
+    #[must_use]
+    pub fn user_agent(mut self, ua: &str) -> Self {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("User-Agent"));
+        self.header("User-Agent", ua)
+    }
+
+    /// Add an HTTP header that every request sends, such as `X-Forwarded-For`.
+    ///
+    /// The authentication method sets its own headers after the extra headers,
+    /// so the two compose. If the name or the value isn't a valid header,
+    /// requests fail with `BugzillaQueryError::Request`.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the maximum number of bug IDs in a single request. The default is 100.
+    ///
+    /// When you access more bugs by their IDs, the IDs are split into several requests,
+    /// because some servers reject the long URL of a single request.
+    /// The value 0 counts as 1.
+    #[must_use]
+    pub fn max_ids_per_request(mut self, n: usize) -> Self {
+        self.max_ids_per_request = n;
+        self
+    }
+
     /// Set Bugzilla fields that this `BzInstance` will request, such as `flags`.
     ///
     /// By default, `BzInstance` requests the `_default` fields, and using this method
     /// overwrites the default value. If you want to set fields in addition
     /// to `_default`, specify `_default` in your list.
+    ///
+    /// Custom fields, which Bugzilla prefixes with `cf_`, are often missing from `_default`.
+    /// Request them by name, such as `cf_fixed_in`, or all at once with `_custom`,
+    /// and read them with `Bug::custom_field`.
+    ///
+    /// The method logs a warning for each field name that it doesn't recognize,
+    /// such as a misspelled `_defualt`, but still requests it.
     #[must_use]
     pub fn include_fields(mut self, fields: Vec<String>) -> Self {
+        for field in fields.iter().filter(|field| !is_known_field(field)) {
+            log::warn!("Unknown Bugzilla field in `include_fields`: {field}");
+        }
         self.included_fields = fields;
         self
     }
 
+    /// Request the flags of bugs, in addition to the fields that are already included.
+    ///
+    /// Without this, `Bug::flags` is `None`, because the `_default` fields don't include flags.
+    /// If no fields are included, which means the Bugzilla default, this requests `_default`
+    /// as well, because a response with only the flags doesn't deserialize into bugs.
+    #[must_use]
+    pub fn with_flags(mut self) -> Self {
+        if self.included_fields.is_empty() {
+            self.included_fields.push("_default".to_string());
+        }
+        if !self.included_fields.iter().any(|field| field == "flags") {
+            self.included_fields.push("flags".to_string());
+        }
+        self
+    }
+
     /// Format the included Bugzilla fields as a URL query fragment, such as `&include_fields=_default,flags`.
     #[must_use]
     fn fields_as_query(&self) -> String {
@@ -141,32 +656,200 @@ impl BzInstance {
         }
     }
 
-    /// Based on the request method, form a complete, absolute URL
+    /// Based on the request method, form a URL path relative to the host
     /// to download the tickets from the REST API.
     #[must_use]
     fn path(&self, method: &Method) -> String {
         format!(
-            "{}/rest/bug?{}{}{}",
-            &self.host,
+            "rest/bug?{}{}{}",
             method.url_fragment(),
             self.fields_as_query(),
             self.pagination.url_fragment()
         )
     }
 
-    /// Download the specified URL using the configured authentication.
-    async fn authenticated_get(&self, url: &str) -> Result<reqwest::Response, reqwest::Error> {
-        let request_builder = self.client.get(url);
-        let authenticated = match &self.auth {
+    /// Form a URL path relative to the host to download a single page of tickets,
+    /// overriding the configured pagination.
+    #[must_use]
+    fn page_path(&self, method: &Method, limit: u32, offset: usize) -> String {
+        format!(
+            "rest/bug?{}{}&limit={limit}&offset={offset}",
+            method.url_fragment(),
+            self.fields_as_query(),
+        )
+    }
+
+    /// Prepare a request to the specified URL with the configured timeout and extra headers.
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.headers.iter().fold(
+            self.client.request(method, url).timeout(self.timeout),
+            |request_builder, (name, value)| request_builder.header(name, value),
+        )
+    }
+
+    /// Add the configured authentication to a request.
+    fn authenticated(&self, request_builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.auth {
             Auth::Anonymous => request_builder,
             Auth::ApiKey(key) => request_builder.header("Authorization", &format!("Bearer {key}")),
             Auth::Basic { user, password } => request_builder.basic_auth(user, Some(password)),
-        };
-        authenticated.send().await
+            // Send the token in a header, rather than in the URL,
+            // which ends up in error messages and logs.
+            Auth::Login { .. } => match self.token.get() {
+                Some(token) => request_builder.header("X-BUGZILLA-TOKEN", token),
+                None => request_builder,
+            },
+        }
+    }
+
+    /// If the authentication method is `Auth::Login`, log in and store the session token,
+    /// unless a previous request has already logged in.
+    async fn log_in(&self) -> Result<(), BugzillaQueryError> {
+        if let Auth::Login { user, password } = &self.auth {
+            self.token
+                .get_or_try_init(|| async {
+                    // The login creates a session token, so it's a write,
+                    // which only ever goes to the primary host.
+                    let url = format!("{}/rest/login", self.host);
+                    // Send the credentials in headers, so that the password never appears
+                    // in the URL, which reqwest includes in its errors.
+                    let response = self
+                        .send_with_retries("rest/login", || {
+                            self.request(reqwest::Method::GET, &url)
+                                .header("X-BUGZILLA-LOGIN", user)
+                                .header("X-BUGZILLA-PASSWORD", password)
+                                .send()
+                        })
+                        .await?;
+
+                    match parse_response::<LoginResponse>(response).await {
+                        Ok(login) => Ok(login.token),
+                        Err(BugzillaQueryError::Api { message, .. }) => {
+                            Err(BugzillaQueryError::Login(message))
+                        }
+                        Err(error) => Err(error),
+                    }
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Download the specified path from the primary host, or from the mirrors,
+    /// using the configured authentication.
+    async fn get_from_hosts(&self, path: &str) -> Result<reqwest::Response, reqwest::Error> {
+        self.send_to_hosts(path, |url| {
+            self.authenticated(self.request(reqwest::Method::GET, url))
+        })
+        .await
+    }
+
+    /// Send the request that `build` prepares for the specified path to the primary host,
+    /// or to the mirrors.
+    ///
+    /// If a host fails with a transient error, try the next one.
+    /// The last host's response is final, whatever it is.
+    async fn send_to_hosts<F>(
+        &self,
+        path: &str,
+        build: F,
+    ) -> Result<reqwest::Response, reqwest::Error>
+    where
+        F: Fn(&str) -> reqwest::RequestBuilder,
+    {
+        let mut host = &self.host;
+        let mut result = build(&format!("{host}/{path}")).send().await;
+
+        for mirror in &self.mirrors {
+            if !is_transient(&result) {
+                break;
+            }
+            log::debug!("Host {host} failed, trying the next mirror: {result:?}");
+            host = mirror;
+            result = build(&format!("{host}/{path}")).send().await;
+        }
+
+        log::debug!("Requested {path} from host {host}");
+        result
+    }
+
+    /// Download the specified path, and retry transient failures with exponential backoff.
+    async fn get_with_retries(&self, path: &str) -> Result<reqwest::Response, reqwest::Error> {
+        self.send_with_retries(path, || self.get_from_hosts(path))
+            .await
+    }
+
+    /// Send the request that `send` makes, and retry transient failures with exponential backoff.
+    /// The path only identifies the request in the logs.
+    async fn send_with_retries<F, Fut>(
+        &self,
+        path: &str,
+        send: F,
+    ) -> Result<reqwest::Response, reqwest::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<reqwest::Response, reqwest::Error>>,
+    {
+        let mut retries = 0;
+
+        loop {
+            let result = send().await;
+
+            if retries >= self.retries || !is_transient(&result) {
+                return result;
+            }
+
+            // Don't let the server stall the request indefinitely with a huge `Retry-After`.
+            let delay = retry_after(&result)
+                .filter(|delay| *delay <= self.max_retry_delay)
+                .unwrap_or_else(|| backoff(self.retry_delay, retries))
+                .min(self.max_retry_delay);
+            log::debug!("Retrying {path} in {delay:?} after a failure: {result:?}");
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+
+    /// Download the specified path and deserialize the JSON response.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, BugzillaQueryError> {
+        self.log_in().await?;
+        let response = self.get_with_retries(path).await?;
+        parse_response(response).await
+    }
+
+    /// Download all pages of tickets that match the request method,
+    /// using the page size from the configured pagination.
+    async fn all_pages(&self, method: &Method<'_>) -> Result<Vec<Bug>, BugzillaQueryError> {
+        let limit = self.pagination.page_size();
+        let mut bugs: Vec<Bug> = Vec::new();
+
+        loop {
+            let path = self.page_path(method, limit, bugs.len());
+            let response: Response = self.get(&path).await?;
+
+            log::debug!("{response:#?}");
+
+            let page_size = response.bugs.len();
+            let total_matches = response.total_matches;
+            bugs.extend(response.bugs);
+
+            if is_last_page(page_size, bugs.len(), total_matches, limit) {
+                break;
+            }
+        }
+
+        Ok(bugs)
     }
 
     /// Access several bugs by their IDs.
-    pub async fn bugs(&self, ids: &[&str]) -> Result<Vec<Bug>, BugzillaQueryError> {
+    ///
+    /// The IDs can be any string type, such as `&[&str]` or `&[String]`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if no bugs match the IDs.
+    pub async fn bugs<S: AsRef<str>>(&self, ids: &[S]) -> Result<Vec<Bug>, BugzillaQueryError> {
         // If the user specifies no IDs, skip network requests and return no bugs.
         // Returning an error could also be valid, but I believe that this behavior
         // is less surprising and more practical.
@@ -174,55 +857,519 @@ impl BzInstance {
             return Ok(Vec::new());
         }
 
-        let url = self.path(&Method::Ids(ids));
-
-        // Gets a bug by ID and deserializes the JSON to data variable
-        let response = self
-            .authenticated_get(&url)
-            .await?
-            .json::<Response>()
-            .await?;
-
-        log::debug!("{:#?}", response);
+        let bugs = self.chunked_ids(ids).await?;
 
         // The resulting list might be empty. In that case, return an error.
-        if response.bugs.is_empty() {
+        if bugs.is_empty() {
             Err(BugzillaQueryError::NoBugs)
         } else {
-            Ok(response.bugs)
+            Ok(bugs)
+        }
+    }
+
+    /// Access several bugs by their IDs, and report which of the IDs are missing
+    /// from the response, because the bugs don't exist or you can't access them.
+    ///
+    /// Returns the bugs, and the missing IDs in the order that you requested them.
+    /// An ID also counts as found if it's an alias of one of the bugs.
+    /// Unlike `bugs`, this doesn't fail if no bugs match the IDs.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails or if Bugzilla reports an error.
+    pub async fn bugs_checked<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+    ) -> Result<(Vec<Bug>, Vec<String>), BugzillaQueryError> {
+        if ids.is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let bugs = self.chunked_ids(ids).await?;
+
+        let missing = ids
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|id| {
+                !bugs
+                    .iter()
+                    .any(|bug| bug.id.to_string() == *id || bug.alias.iter().any(|a| a == id))
+            })
+            .map(ToString::to_string)
+            .collect();
+
+        Ok((bugs, missing))
+    }
+
+    /// Download the bugs with the specified IDs.
+    ///
+    /// Long lists of IDs result in URLs that are too long for some servers.
+    /// Request the IDs in chunks, one after another, to keep the order of the bugs.
+    async fn chunked_ids<S: AsRef<str>>(&self, ids: &[S]) -> Result<Vec<Bug>, BugzillaQueryError> {
+        let mut bugs = Vec::new();
+
+        for chunk in ids.chunks(self.max_ids_per_request.max(1)) {
+            let ids: Vec<&str> = chunk.iter().map(AsRef::as_ref).collect();
+            let path = self.path(&Method::Ids(ids));
+
+            // Gets a bug by ID and deserializes the JSON to data variable
+            let response: Response = self.get(&path).await?;
+
+            log::debug!("{response:#?}");
+
+            bugs.extend(response.bugs);
+        }
+
+        Ok(bugs)
+    }
+
+    /// Access several bugs by their IDs, and return the complete response from Bugzilla
+    /// as untyped JSON, without mapping it into bugs.
+    ///
+    /// This is useful for debugging, or if the response doesn't deserialize into `Bug`,
+    /// such as after a schema change in a new Bugzilla version. All IDs go in a single request.
+    /// If you specify no IDs, this skips the request and returns `{"bugs": []}`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if the response isn't JSON, or if Bugzilla reports an error.
+    pub async fn bugs_raw<S: AsRef<str>>(&self, ids: &[S]) -> Result<Value, BugzillaQueryError> {
+        // Like `bugs`, skip network requests if the user specifies no IDs.
+        if ids.is_empty() {
+            return Ok(serde_json::json!({ "bugs": [] }));
+        }
+
+        let ids: Vec<&str> = ids.iter().map(AsRef::as_ref).collect();
+        let path = self.path(&Method::Ids(ids));
+
+        let response: Value = self.get(&path).await?;
+
+        log::debug!("{response:#?}");
+
+        // An error report is valid JSON, so check for it explicitly.
+        match BugzillaError::deserialize(&response) {
+            Ok(error) if error.error => Err(BugzillaQueryError::Api {
+                code: error.code,
+                message: error.message,
+            }),
+            _ => Ok(response),
         }
     }
 
     /// Access a single bug by its ID.
+    ///
+    /// Bugzilla also resolves an alias in place of the ID,
+    /// but `bug_by_alias` makes the intent explicit.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
     pub async fn bug(&self, id: &str) -> Result<Bug, BugzillaQueryError> {
         // Reuse the `bugs` function. Later, extract the first element.
-        let bugs = self.bugs(&[id]).await?;
+        // If the response is empty, report the ID that the user requested.
+        let bugs = match self.bugs(&[id]).await {
+            Err(BugzillaQueryError::NoBugs) => {
+                return Err(BugzillaQueryError::NotFound(id.to_string()))
+            }
+            result => result?,
+        };
 
         // This is a way to return the first (and only) element of the Vec,
         // without cloning it.
-        bugs.into_iter().next().ok_or(BugzillaQueryError::NoBugs)
+        bugs.into_iter()
+            .next()
+            .ok_or_else(|| BugzillaQueryError::NotFound(id.to_string()))
+    }
+
+    /// Access a single bug by its alias, which is a unique textual name of the bug.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if no bug has the alias.
+    pub async fn bug_by_alias(&self, alias: &str) -> Result<Bug, BugzillaQueryError> {
+        let path = self.path(&Method::Alias(alias));
+
+        let response: Response = self.get(&path).await?;
+
+        log::debug!("{response:#?}");
+
+        response
+            .bugs
+            .into_iter()
+            .next()
+            .ok_or_else(|| BugzillaQueryError::NotFound(alias.to_string()))
     }
 
     /// Access bugs using a free-form Bugzilla search query.
     ///
     /// An example of a query: `component=rust&product=Fedora&version=36`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if no bugs match the query.
     pub async fn search(&self, query: &str) -> Result<Vec<Bug>, BugzillaQueryError> {
-        let url = self.path(&Method::Search(query));
+        let page = self.search_with_meta(query).await?;
 
-        // Gets the bugs by query and deserializes the JSON to data variable
-        let response = self
-            .authenticated_get(&url)
-            .await?
-            .json::<Response>()
-            .await?;
+        // The resulting list might be empty. In that case, return an error.
+        if page.bugs.is_empty() {
+            Err(BugzillaQueryError::NoBugs)
+        } else {
+            Ok(page.bugs)
+        }
+    }
+
+    /// Access several bugs by their IDs in a single request,
+    /// together with the number of matching bugs and the offset that Bugzilla reports.
+    ///
+    /// Unlike `bugs`, this doesn't split long lists of IDs into several requests,
+    /// and doesn't fail if no bugs match the IDs.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails or if Bugzilla reports an error.
+    pub async fn bugs_with_meta<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+    ) -> Result<BugPage, BugzillaQueryError> {
+        let ids: Vec<&str> = ids.iter().map(AsRef::as_ref).collect();
+        self.page(&Method::Ids(ids)).await
+    }
+
+    /// Access bugs using a free-form Bugzilla search query,
+    /// together with the number of matching bugs and the offset that Bugzilla reports.
+    ///
+    /// Use the metadata to find out if the server truncated the results.
+    /// Unlike `search`, this doesn't fail if no bugs match the query.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails or if Bugzilla reports an error.
+    pub async fn search_with_meta(&self, query: &str) -> Result<BugPage, BugzillaQueryError> {
+        self.page(&Method::Search(query)).await
+    }
 
-        log::debug!("{:#?}", response);
+    /// Download a single response of bugs with the configured pagination, and keep its metadata.
+    async fn page(&self, method: &Method<'_>) -> Result<BugPage, BugzillaQueryError> {
+        let path = self.path(method);
+
+        // Gets the bugs and deserializes the JSON to data variable
+        let response: Response = self.get(&path).await?;
+
+        log::debug!("{response:#?}");
+
+        Ok(BugPage::from(response))
+    }
+
+    /// Access several bugs by their IDs, downloading them in as many requests as necessary.
+    ///
+    /// Some Bugzilla instances cap the number of bugs in a response, even with
+    /// `Pagination::Unlimited`. This method requests the bugs page by page
+    /// and concatenates the results. The page size is the number set by `Pagination::Limit`,
+    /// or by `Pagination::Page`, or 100 with other pagination settings.
+    /// The offset in `Pagination::Page` doesn't apply here.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any request fails, if Bugzilla reports an error, or if no bugs match the IDs.
+    pub async fn bugs_all<S: AsRef<str>>(&self, ids: &[S]) -> Result<Vec<Bug>, BugzillaQueryError> {
+        // Consistently with `bugs`, no IDs result in no bugs.
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut bugs = Vec::new();
+
+        // Request the IDs in chunks, the same way as `bugs`.
+        for chunk in ids.chunks(self.max_ids_per_request.max(1)) {
+            let ids: Vec<&str> = chunk.iter().map(AsRef::as_ref).collect();
+            bugs.extend(self.all_pages(&Method::Ids(ids)).await?);
+        }
 
         // The resulting list might be empty. In that case, return an error.
-        if response.bugs.is_empty() {
+        if bugs.is_empty() {
             Err(BugzillaQueryError::NoBugs)
         } else {
-            Ok(response.bugs)
+            Ok(bugs)
+        }
+    }
+
+    /// Access bugs using a free-form Bugzilla search query,
+    /// downloading them in as many requests as necessary.
+    ///
+    /// See `bugs_all` for details on the pagination.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any request fails, if Bugzilla reports an error, or if no bugs match the query.
+    pub async fn search_all(&self, query: &str) -> Result<Vec<Bug>, BugzillaQueryError> {
+        let bugs = self.all_pages(&Method::Search(query)).await?;
+
+        // The resulting list might be empty. In that case, return an error.
+        if bugs.is_empty() {
+            Err(BugzillaQueryError::NoBugs)
+        } else {
+            Ok(bugs)
+        }
+    }
+
+    /// Iterate over the bugs that match a free-form Bugzilla search query,
+    /// downloading the results page by page as needed.
+    ///
+    /// See `bugs_all` for details on the pagination. Unlike `search`,
+    /// the iterator produces no bugs rather than an error if no bugs match.
+    #[must_use]
+    pub fn search_iter(&self, query: &str) -> SearchIter<'_> {
+        SearchIter {
+            instance: self,
+            query: query.to_string(),
+            limit: self.pagination.page_size(),
+            downloaded: 0,
+            page: Vec::new().into_iter(),
+            finished: false,
+        }
+    }
+
+    /// Access the comments in a single bug by the bug ID.
+    ///
+    /// The first comment is the description of the bug.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
+    pub async fn comments(&self, id: &str) -> Result<Vec<Comment>, BugzillaQueryError> {
+        let path = format!("rest/bug/{id}/comment");
+
+        let response: CommentsResponse = self.get(&path).await?;
+
+        log::debug!("{response:#?}");
+
+        // Bugzilla keys the comments by the numeric bug ID, even if the request
+        // used an alias. Only one bug was requested, so take the only entry.
+        response
+            .bugs
+            .into_values()
+            .next()
+            .map(|bug| bug.comments)
+            .ok_or_else(|| BugzillaQueryError::NotFound(id.to_string()))
+    }
+
+    /// Access the comments in several bugs by their IDs, keyed by the numeric bug ID.
+    ///
+    /// This downloads the comments of many bugs in as few requests as possible,
+    /// split by `max_ids_per_request`. Bugs without comments are missing from the map.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails or if Bugzilla reports an error, such as when a bug does not exist.
+    pub async fn comments_for<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+    ) -> Result<HashMap<i32, Vec<Comment>>, BugzillaQueryError> {
+        let mut comments = HashMap::new();
+
+        for chunk in ids.chunks(self.max_ids_per_request.max(1)) {
+            // The comment endpoint takes the first bug in the path, and any other bugs as parameters.
+            let (first, rest) = match chunk.split_first() {
+                Some(split) => split,
+                None => continue,
+            };
+            let path = rest.iter().enumerate().fold(
+                format!("rest/bug/{}/comment", first.as_ref()),
+                |path, (index, id)| {
+                    let separator = if index == 0 { '?' } else { '&' };
+                    format!("{path}{separator}ids={}", id.as_ref())
+                },
+            );
+
+            let response: CommentsResponse = self.get(&path).await?;
+
+            log::debug!("{response:#?}");
+
+            comments.extend(
+                response
+                    .bugs
+                    .into_iter()
+                    .map(|(id, bug)| (id, bug.comments)),
+            );
+        }
+
+        Ok(comments)
+    }
+
+    /// Download the attachments in a single bug from the specified path.
+    async fn attachments_at(
+        &self,
+        id: &str,
+        path: &str,
+    ) -> Result<Vec<Attachment>, BugzillaQueryError> {
+        let response: AttachmentsResponse = self.get(path).await?;
+
+        log::debug!("{response:#?}");
+
+        // Bugzilla keys the attachments by the numeric bug ID, even if the request
+        // used an alias. Only one bug was requested, so take the only entry.
+        response
+            .bugs
+            .into_values()
+            .next()
+            .ok_or_else(|| BugzillaQueryError::NotFound(id.to_string()))
+    }
+
+    /// Access the files attached to a single bug by the bug ID,
+    /// including their content.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
+    pub async fn attachments(&self, id: &str) -> Result<Vec<Attachment>, BugzillaQueryError> {
+        let path = format!("rest/bug/{id}/attachment");
+        self.attachments_at(id, &path).await
+    }
+
+    /// Access the files attached to a single bug by the bug ID,
+    /// without their content. The `data` field in each attachment is `None`.
+    ///
+    /// This is much cheaper than `attachments` if the bug has large attachments.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
+    pub async fn attachment_metadata(
+        &self,
+        id: &str,
+    ) -> Result<Vec<Attachment>, BugzillaQueryError> {
+        let path = format!("rest/bug/{id}/attachment?exclude_fields=data");
+        self.attachments_at(id, &path).await
+    }
+
+    /// Download the history of a single bug from the specified path.
+    async fn history_at(
+        &self,
+        id: &str,
+        path: &str,
+    ) -> Result<Vec<HistoryEntry>, BugzillaQueryError> {
+        let response: HistoryResponse = self.get(path).await?;
+
+        log::debug!("{response:#?}");
+
+        // Only one bug was requested, so take the only entry.
+        response
+            .bugs
+            .into_iter()
+            .next()
+            .map(|bug| bug.history)
+            .ok_or_else(|| BugzillaQueryError::NotFound(id.to_string()))
+    }
+
+    /// Access the history of changes in a single bug by the bug ID.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
+    pub async fn history(&self, id: &str) -> Result<Vec<HistoryEntry>, BugzillaQueryError> {
+        let path = format!("rest/bug/{id}/history");
+        self.history_at(id, &path).await
+    }
+
+    /// Access the history of changes in a single bug by the bug ID,
+    /// limited to the changes newer than the specified time.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, if Bugzilla reports an error, or if the bug does not exist.
+    pub async fn history_since(
+        &self,
+        id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<HistoryEntry>, BugzillaQueryError> {
+        let since = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let path = format!("rest/bug/{id}/history?new_since={since}");
+        self.history_at(id, &path).await
+    }
+
+    /// Access the user account that this instance is logged in as.
+    ///
+    /// Use this to verify the credentials before making other requests.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails, or if Bugzilla reports an error,
+    /// such as when the instance uses `Auth::Anonymous` or the credentials are invalid.
+    pub async fn whoami(&self) -> Result<User, BugzillaQueryError> {
+        let user: User = self.get("rest/whoami").await?;
+
+        log::debug!("{user:#?}");
+
+        Ok(user)
+    }
+
+    /// Modify a bug by its ID or alias. Only the fields that are set in `changes` change.
+    ///
+    /// The update always goes to the primary host, never to a mirror,
+    /// and it isn't retried, so that a comment can't end up in the bug twice.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the instance uses `Auth::Anonymous`, if the request fails,
+    /// or if Bugzilla reports an error, such as a missing permission or an invalid status.
+    pub async fn update_bug(
+        &self,
+        id: &str,
+        changes: &BugUpdate,
+    ) -> Result<(), BugzillaQueryError> {
+        if let Auth::Anonymous = self.auth {
+            return Err(BugzillaQueryError::AuthRequired);
+        }
+
+        self.log_in().await?;
+
+        let url = format!("{}/rest/bug/{id}", self.host);
+        let response = self
+            .authenticated(self.request(reqwest::Method::PUT, &url))
+            .json(changes)
+            .send()
+            .await?;
+        let response: UpdateResponse = parse_response(response).await?;
+
+        log::debug!("{response:#?}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::is_known_field;
+
+    /// Check that the list of known fields covers every field in a real Bugzilla response,
+    /// so that requesting them doesn't log a warning.
+    #[test]
+    fn known_fields_cover_bug_fixture() {
+        let bug: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(include_str!("../tests/fixtures/bug.json")).unwrap();
+
+        for field in bug.keys() {
+            if field.ends_with("_detail") || field.starts_with("cf_") {
+                continue;
+            }
+            assert!(is_known_field(field), "Unknown field: {field}");
+        }
+    }
+
+    /// Check the fields that the documentation of `HeatScorer` recommends.
+    #[test]
+    fn known_fields_include_heat_fields() {
+        for field in [
+            "votes",
+            "comments",
+            "cc",
+            "severity",
+            "priority",
+            "last_change_time",
+        ] {
+            assert!(is_known_field(field), "Unknown field: {field}");
         }
+        assert!(!is_known_field("_defualt"));
     }
 }
@@ -14,7 +14,14 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::bug_model::Bug;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::access::Pagination;
+use crate::bug_model::{Bug, BugPage, Change, Comment, HistoryEntry};
+use crate::field_enums::{Priority, Resolution, Severity, Status};
+use crate::heat::HeatScorer;
 
 impl Bug {
     /// Returns a the value of the flag corresponding to the flag name.
@@ -23,14 +30,169 @@ impl Bug {
     /// # Panics
     ///
     /// The function panics if flags are not available at all.
-    /// Enable flags when accessing the Bugzilla instance.
+    /// Enable flags when accessing the Bugzilla instance with `BzInstance::with_flags`.
     #[must_use]
-    pub fn get_flag<'a>(&'a self, name: &str) -> Option<&'a str> {
+    #[allow(clippy::expect_used)]
+    pub fn get_flag(&self, name: &str) -> Option<&str> {
         let flags = self
             .flags
             .as_ref()
             .expect("The bug has no flags. Enable flags when accessing Bugzilla.");
         let flag = flags.iter().find(|f| f.name == name)?;
-        Some(&flag.status)
+        Some(flag.status.as_str())
+    }
+
+    /// Returns the value of a custom field, such as `cf_fixed_in`.
+    /// If the bug has no field by that name, the function returns None.
+    ///
+    /// Custom fields are only available if the request includes them.
+    /// See `BzInstance::include_fields`.
+    #[must_use]
+    pub fn custom_field(&self, name: &str) -> Option<&Value> {
+        self.extra.get(name)
+    }
+
+    /// Returns the value of a custom field, deserialized into the specified type.
+    /// If the bug has no field by that name, the function returns None.
+    ///
+    /// ```no_run
+    /// # fn example(bug: bugzilla_query::Bug) {
+    /// let fixed_in: Option<String> = bug.custom_field_as("cf_fixed_in").and_then(Result::ok);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn custom_field_as<T: DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> Option<Result<T, serde_json::Error>> {
+        self.custom_field(name).map(T::deserialize)
+    }
+
+    /// Returns the names of all custom fields in the bug.
+    /// Bugzilla prefixes the names of custom fields with `cf_`.
+    pub fn custom_fields(&self) -> impl Iterator<Item = &str> {
+        self.extra
+            .as_object()
+            .into_iter()
+            .flat_map(serde_json::Map::keys)
+            .map(String::as_str)
+            .filter(|name| name.starts_with("cf_"))
+    }
+
+    /// The status of the bug as a typed enum. The `status` field keeps the raw value.
+    #[must_use]
+    pub fn status_enum(&self) -> Status {
+        Status::from(self.status.as_str())
+    }
+
+    /// The resolution of the bug as a typed enum. The `resolution` field keeps the raw value.
+    #[must_use]
+    pub fn resolution_enum(&self) -> Resolution {
+        Resolution::from(self.resolution.as_str())
+    }
+
+    /// The severity of the bug as a typed enum. The `severity` field keeps the raw value.
+    #[must_use]
+    pub fn severity_enum(&self) -> Severity {
+        Severity::from(self.severity.as_str())
+    }
+
+    /// The priority of the bug as a typed enum. The `priority` field keeps the raw value.
+    #[must_use]
+    pub fn priority_enum(&self) -> Priority {
+        Priority::from(self.priority.as_str())
+    }
+
+    /// Compute the heat score of the bug at the current time.
+    /// See `HeatScorer` for the formula.
+    #[must_use]
+    pub fn heat(&self, scorer: &HeatScorer) -> f64 {
+        self.heat_at(scorer, Utc::now())
+    }
+
+    /// Compute the heat score of the bug as of the specified time,
+    /// which makes the score reproducible.
+    /// See `HeatScorer` for the formula.
+    // The counts are far too small to lose precision as floats.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn heat_at(&self, scorer: &HeatScorer, now: DateTime<Utc>) -> f64 {
+        let comments = self
+            .extra
+            .get("comments")
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len);
+        let votes = self
+            .extra
+            .get("votes")
+            .and_then(Value::as_f64)
+            .unwrap_or_default();
+        // A change in the future counts as a change right now.
+        let days = (now - self.last_change_time).num_seconds().max(0) as f64 / 86_400.0;
+
+        scorer.cc * self.cc.len() as f64
+            + scorer.comments * comments as f64
+            + scorer.severity * f64::from(self.severity_enum().rank())
+            + scorer.priority * f64::from(self.priority_enum().rank())
+            + scorer.recency / (1.0 + days)
+            + scorer.votes * votes
+    }
+}
+
+impl Comment {
+    /// Check if this comment is the description of the bug,
+    /// which Bugzilla stores as the first comment.
+    #[must_use]
+    pub fn is_description(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl HistoryEntry {
+    /// Returns the change of the specified field in this entry, such as `status`.
+    /// If the entry didn't change the field, the function returns None.
+    #[must_use]
+    pub fn change(&self, field_name: &str) -> Option<&Change> {
+        self.changes
+            .iter()
+            .find(|change| change.field_name == field_name)
+    }
+}
+
+impl BugPage {
+    /// Check if more bugs match the request beyond this page.
+    ///
+    /// If Bugzilla doesn't report `total_matches`, a full page counts as having more bugs.
+    #[must_use]
+    pub fn has_more(&self) -> bool {
+        let received = self.offset.unwrap_or(0) as usize + self.bugs.len();
+        match (self.total_matches, self.limit) {
+            (Some(total), _) => received < total as usize,
+            (None, Some(limit)) => limit > 0 && self.bugs.len() >= limit as usize,
+            (None, None) => false,
+        }
+    }
+
+    /// The pagination that requests the page after this one, or `None` if this is the last page.
+    ///
+    /// The next page has the same size as this one. Pass the pagination
+    /// to `BzInstance::paginate` to page through the results without keeping any other state.
+    #[must_use]
+    pub fn next_page(&self) -> Option<Pagination> {
+        if self.bugs.is_empty() || !self.has_more() {
+            return None;
+        }
+
+        // Without a reported limit, take the number of bugs that the server actually returned.
+        let received = u32::try_from(self.bugs.len()).ok()?;
+        let limit = match self.limit {
+            Some(limit) if limit > 0 => limit,
+            _ => received,
+        };
+
+        Some(Pagination::Page {
+            limit,
+            offset: self.offset.unwrap_or(0) + received,
+        })
     }
 }
@@ -18,14 +18,20 @@ limitations under the License.
 //! Any extra fields that come from a custom Bugzilla configuration are captured
 //! in the `extra` hash map in the parent struct.
 
+use std::collections::HashMap;
 use std::fmt;
 
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::Deserialize;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 
+use crate::field_enums::FlagStatus;
+
 /// The response from Bugzilla, which includes the list of requested bugs
 /// and some additional metadata.
+// Some of the metadata is only displayed in debug logs for now.
+#[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct Response {
     pub offset: Option<u32>,
@@ -36,7 +42,77 @@ pub struct Response {
     pub extra: Value,
 }
 
+/// The response from Bugzilla to an update of bugs.
+// The details of the changes are only displayed in debug logs for now.
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+pub struct UpdateResponse {
+    pub bugs: Vec<Value>,
+}
+
+/// A single response of bugs, together with the metadata that Bugzilla reports about it.
+///
+/// If `total_matches` is larger than the number of bugs, the server truncated
+/// the results, and you can request the rest with `Pagination::Page`.
+/// Not every Bugzilla instance reports the metadata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BugPage {
+    pub bugs: Vec<Bug>,
+    /// The number of bugs that match the request, across all pages.
+    pub total_matches: Option<u32>,
+    /// The number of matching bugs that precede this page.
+    pub offset: Option<u32>,
+    /// The maximum number of bugs in this page. Zero means no limit.
+    pub limit: Option<u32>,
+}
+
+impl From<Response> for BugPage {
+    fn from(response: Response) -> Self {
+        Self {
+            bugs: response.bugs,
+            total_matches: response.total_matches,
+            offset: response.offset,
+            // Bugzilla reports the limit as a string.
+            limit: response.limit.and_then(|limit| limit.parse().ok()),
+        }
+    }
+}
+
+/// The response from Bugzilla to a request for comments.
+/// The comments are keyed by the bug ID.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommentsResponse {
+    pub bugs: HashMap<i32, BugComments>,
+}
+
+/// The comments that belong to a single bug in a `CommentsResponse`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BugComments {
+    pub comments: Vec<Comment>,
+}
+
+/// The response from Bugzilla to a request for attachments.
+/// The attachments are keyed by the bug ID.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AttachmentsResponse {
+    pub bugs: HashMap<i32, Vec<Attachment>>,
+}
+
+/// The response from Bugzilla to a request for the history of bugs.
+#[derive(Clone, Debug, Deserialize)]
+pub struct HistoryResponse {
+    pub bugs: Vec<BugHistory>,
+}
+
+/// The history of a single bug in a `HistoryResponse`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BugHistory {
+    pub history: Vec<HistoryEntry>,
+}
+
 /// An error report from Bugzilla.
+// The crate only passes on the code and the message.
+#[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct BugzillaError {
     pub error: bool,
@@ -75,6 +151,7 @@ pub struct Bug {
     pub creator_detail: User,
     pub summary: String,
     pub status: String,
+    #[serde(default, deserialize_with = "deserialize_optional_hours")]
     pub estimated_time: Option<i64>,
     pub target_milestone: String,
     pub cc: Vec<String>,
@@ -90,6 +167,7 @@ pub struct Bug {
     pub product: String,
     pub platform: String,
     pub last_change_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_optional_hours")]
     pub remaining_time: Option<i64>,
     pub priority: String,
     pub whiteboard: String,
@@ -99,6 +177,7 @@ pub struct Bug {
     pub qa_contact_detail: Option<User>,
     pub dupe_of: Option<i32>,
     pub target_release: Option<Version>,
+    #[serde(default, deserialize_with = "deserialize_optional_hours")]
     pub actual_time: Option<i64>,
     pub component: Component,
     pub is_cc_accessible: bool,
@@ -109,10 +188,13 @@ pub struct Bug {
     pub see_also: Option<Vec<String>>,
     pub groups: Vec<String>,
     /// Bugzilla stores `deadline` only as `YYYY-MM-DD`, so it can't deserialize to full `DateTime`.
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
     pub deadline: Option<NaiveDate>,
     pub update_token: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_hours")]
     pub work_time: Option<i64>,
     // Not part of the default response:
+    /// `None` unless the request includes the `flags` field. See `BzInstance::with_flags`.
     pub flags: Option<Vec<Flag>>,
     pub tags: Option<Vec<String>>,
     pub dependent_products: Option<Vec<String>>,
@@ -123,6 +205,8 @@ pub struct Bug {
 /// The representation of a Bugzilla user account.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 pub struct User {
+    /// Empty if Bugzilla doesn't report the email, such as in the response to `BzInstance::whoami`.
+    #[serde(default)]
     pub email: String,
     pub id: i32,
     pub name: String,
@@ -131,6 +215,67 @@ pub struct User {
     pub extra: Value,
 }
 
+/// The representation of a comment in a bug.
+/// The comment with `count` 0 is the description of the bug.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct Comment {
+    pub id: i32,
+    pub bug_id: i32,
+    pub attachment_id: Option<i32>,
+    pub count: i32,
+    pub text: String,
+    pub creator: String,
+    pub time: DateTime<Utc>,
+    pub creation_time: DateTime<Utc>,
+    pub is_private: bool,
+    pub tags: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// The representation of a file attached to a bug.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: i32,
+    pub bug_id: i32,
+    pub file_name: String,
+    pub summary: String,
+    pub content_type: String,
+    pub size: u64,
+    pub creator: String,
+    pub creation_time: DateTime<Utc>,
+    pub last_change_time: DateTime<Utc>,
+    pub is_patch: bool,
+    pub is_obsolete: bool,
+    pub is_private: bool,
+    /// The content of the file, encoded in base64.
+    pub data: Option<String>,
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// A set of changes that a user made to a bug at the same time.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub when: DateTime<Utc>,
+    pub who: String,
+    pub changes: Vec<Change>,
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
+/// A change of a single field in a `HistoryEntry`.
+/// If the change concerns an attachment, `attachment_id` identifies it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct Change {
+    pub field_name: String,
+    pub removed: String,
+    pub added: String,
+    pub attachment_id: Option<i32>,
+    #[serde(flatten)]
+    pub extra: Value,
+}
+
 /// The representation of a flag in a bug.
 /// A flag resembles a hash map entry, where `flag.name` is the key
 /// and `flag.status` is the value.
@@ -141,8 +286,10 @@ pub struct Flag {
     pub creation_date: DateTime<Utc>,
     pub modification_date: DateTime<Utc>,
     pub name: String,
-    pub status: String,
+    pub status: FlagStatus,
+    #[serde(deserialize_with = "deserialize_user_name")]
     pub setter: String,
+    #[serde(default, deserialize_with = "deserialize_optional_user_name")]
     pub requestee: Option<String>,
     #[serde(flatten)]
     pub extra: Value,
@@ -154,3 +301,71 @@ impl fmt::Display for Flag {
         write!(f, "{}: {}", self.name, self.status)
     }
 }
+
+/// Some Bugzilla instances identify the user in a flag by the login name,
+/// some use an object with the user details.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UserName {
+    Name(String),
+    Detail { name: String },
+}
+
+impl From<UserName> for String {
+    fn from(user: UserName) -> Self {
+        match user {
+            UserName::Name(name) | UserName::Detail { name } => name,
+        }
+    }
+}
+
+/// Deserialize a user in either representation as the login name.
+fn deserialize_user_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    UserName::deserialize(deserializer).map(String::from)
+}
+
+/// Deserialize an optional user in either representation as the login name.
+fn deserialize_optional_user_name<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<UserName>::deserialize(deserializer).map(|user| user.map(String::from))
+}
+
+/// Deserialize an optional `YYYY-MM-DD` date. Some Bugzilla instances send
+/// an empty string rather than `null` if the date is not set.
+fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)?.as_deref() {
+        None | Some("") => Ok(None),
+        Some(date) => date.parse().map(Some).map_err(D::Error::custom),
+    }
+}
+
+/// A number of hours, which some Bugzilla instances report as a floating-point number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Hours {
+    Whole(i64),
+    Fractional(f64),
+}
+
+/// Deserialize an optional number of hours from an integer, a floating-point number, or `null`.
+/// Rounds fractional hours to the nearest whole hour.
+fn deserialize_optional_hours<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Hours>::deserialize(deserializer)? {
+        None => None,
+        Some(Hours::Whole(hours)) => Some(hours),
+        // Saturating at the bounds of `i64` is fine for any realistic number of hours.
+        #[allow(clippy::cast_possible_truncation)]
+        Some(Hours::Fractional(hours)) => Some(hours.round() as i64),
+    })
+}
@@ -0,0 +1,117 @@
+/*
+Copyright 2022 Marek Suchánek <msuchane@redhat.com>
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! This module provides the changes that you can make to a bug with `BzInstance::update_bug`.
+
+use serde::Serialize;
+
+use crate::field_enums::{Resolution, Status};
+
+/// The changes to a single bug. Only the fields that you set are sent to Bugzilla,
+/// and the other fields of the bug stay the same.
+///
+/// ```
+/// use bugzilla_query::{BugUpdate, Resolution, Status};
+///
+/// let update = BugUpdate::default()
+///     .status(Status::Closed)
+///     .resolution(Resolution::Errata)
+///     .comment("Fixed in the 1.2 release.");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct BugUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<NewComment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<CcUpdate>,
+}
+
+/// A comment to add to a bug as part of an update.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NewComment {
+    pub body: String,
+    pub is_private: bool,
+}
+
+/// The users to add to or remove from the CC list of a bug, by their login names.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CcUpdate {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub add: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remove: Vec<String>,
+}
+
+impl BugUpdate {
+    /// Change the status of the bug.
+    #[must_use]
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Change the resolution of the bug. Bugzilla usually requires a resolution
+    /// when you change the status to a closed one.
+    #[must_use]
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Add a public comment to the bug.
+    #[must_use]
+    pub fn comment(mut self, body: &str) -> Self {
+        self.comment = Some(NewComment {
+            body: body.to_string(),
+            is_private: false,
+        });
+        self
+    }
+
+    /// Add a private comment to the bug, which only privileged users can see.
+    #[must_use]
+    pub fn private_comment(mut self, body: &str) -> Self {
+        self.comment = Some(NewComment {
+            body: body.to_string(),
+            is_private: true,
+        });
+        self
+    }
+
+    /// Add a user to the CC list of the bug.
+    #[must_use]
+    pub fn add_cc(mut self, user: &str) -> Self {
+        self.cc
+            .get_or_insert_with(CcUpdate::default)
+            .add
+            .push(user.to_string());
+        self
+    }
+
+    /// Remove a user from the CC list of the bug.
+    #[must_use]
+    pub fn remove_cc(mut self, user: &str) -> Self {
+        self.cc
+            .get_or_insert_with(CcUpdate::default)
+            .remove
+            .push(user.to_string());
+        self
+    }
+}
@@ -23,6 +23,53 @@ pub enum BugzillaQueryError {
     MissingBugs(Vec<String>),
     #[error("The Bugzilla query returned no bugs.")]
     NoBugs,
+    #[error("The bug does not exist or is not accessible: {0}.")]
+    NotFound(String),
     #[error("Error in accessing the Bugzilla REST API.")]
-    Request(#[from] reqwest::Error),
+    Request(#[source] reqwest::Error),
+    #[error("The request to Bugzilla timed out.")]
+    Timeout(#[source] reqwest::Error),
+    #[error("Failed to parse the Bugzilla response.")]
+    Deserialization(#[from] serde_json::Error),
+    #[error("Bugzilla reported an error (code {code}): {message}")]
+    Api { code: i32, message: String },
+    #[error("Failed to log into Bugzilla: {0}")]
+    Login(String),
+    #[error("The Bugzilla host is not a valid http or https URL: {0}")]
+    InvalidHost(String),
+    #[error("Modifying bugs requires authentication.")]
+    AuthRequired,
+    #[error("Failed to configure TLS for the HTTP client.")]
+    Tls(#[source] reqwest::Error),
+}
+
+// Report timeouts separately, so that users can tell a hung instance from other failures.
+impl From<reqwest::Error> for BugzillaQueryError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout(error)
+        } else {
+            Self::Request(error)
+        }
+    }
+}
+
+/// The Bugzilla error code that means that you lack the permission to access a bug.
+const ACCESS_DENIED: i32 = 102;
+
+impl BugzillaQueryError {
+    /// The error code that Bugzilla reported, if this is an error report from Bugzilla.
+    #[must_use]
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Self::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Check if Bugzilla denied access to a bug because you lack the permission to see it.
+    #[must_use]
+    pub fn is_access_denied(&self) -> bool {
+        self.code() == Some(ACCESS_DENIED)
+    }
 }
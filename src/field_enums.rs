@@ -0,0 +1,226 @@
+/*
+Copyright 2022 Marek Suchánek <msuchane@redhat.com>
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! This module provides typed enums for bug fields that Bugzilla stores as free-form strings.
+//! Each enum lists the common values, and preserves any other value in the `Other` variant,
+//! because every Bugzilla instance can configure its own values.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Some Bugzilla instances report a field value as a number, rather than as a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValue {
+    Text(String),
+    Number(serde_json::Number),
+}
+
+/// Define an enum of the common values of a field, with the catch-all `Other` variant.
+/// Converting from a string ignores ASCII case. Displaying the enum produces
+/// the canonical value. Deserializing never fails on an unknown string or a number,
+/// and serializing produces the value as Bugzilla reports it.
+macro_rules! field_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)+ }) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($variant,)+
+            /// Any other value, exactly as Bugzilla reports it.
+            Other(String),
+        }
+
+        impl $name {
+            /// The value as Bugzilla reports it.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                $(
+                    if value.eq_ignore_ascii_case($value) {
+                        return Self::$variant;
+                    }
+                )+
+                Self::Other(value.to_string())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = match RawValue::deserialize(deserializer)? {
+                    RawValue::Text(text) => text,
+                    RawValue::Number(number) => number.to_string(),
+                };
+                Ok(Self::from(value.as_str()))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+    };
+}
+
+field_enum! {
+    /// The status of a bug. Covers the default Bugzilla workflow and the Red Hat workflow.
+    ///
+    /// Which statuses count as open depends on the configuration of the Bugzilla instance.
+    /// To check if a bug is open, use the `Bug::is_open` field, which Bugzilla sets itself.
+    Status {
+        Unconfirmed => "UNCONFIRMED",
+        New => "NEW",
+        Confirmed => "CONFIRMED",
+        Assigned => "ASSIGNED",
+        InProgress => "IN_PROGRESS",
+        Post => "POST",
+        Modified => "MODIFIED",
+        OnDev => "ON_DEV",
+        OnQa => "ON_QA",
+        Verified => "VERIFIED",
+        ReleasePending => "RELEASE_PENDING",
+        Reopened => "REOPENED",
+        Resolved => "RESOLVED",
+        Closed => "CLOSED",
+    }
+}
+
+field_enum! {
+    /// The resolution of a bug. Covers the default Bugzilla resolutions and the Red Hat ones.
+    /// A bug that isn't resolved has an empty resolution, which is `Unresolved`.
+    Resolution {
+        Unresolved => "",
+        Fixed => "FIXED",
+        Invalid => "INVALID",
+        WontFix => "WONTFIX",
+        Duplicate => "DUPLICATE",
+        WorksForMe => "WORKSFORME",
+        Moved => "MOVED",
+        NotABug => "NOTABUG",
+        CurrentRelease => "CURRENTRELEASE",
+        NextRelease => "NEXTRELEASE",
+        Errata => "ERRATA",
+        Rawhide => "RAWHIDE",
+        Upstream => "UPSTREAM",
+        CantFix => "CANTFIX",
+        InsufficientData => "INSUFFICIENT_DATA",
+        Deferred => "DEFERRED",
+        Eol => "EOL",
+    }
+}
+
+field_enum! {
+    /// The severity of a bug. Covers the Red Hat severities, the classic Bugzilla severities,
+    /// and the Mozilla `S1` to `S4` severities.
+    Severity {
+        Urgent => "urgent",
+        High => "high",
+        Medium => "medium",
+        Low => "low",
+        Unspecified => "unspecified",
+        Blocker => "blocker",
+        Critical => "critical",
+        Major => "major",
+        Normal => "normal",
+        Minor => "minor",
+        Trivial => "trivial",
+        Enhancement => "enhancement",
+        S1 => "S1",
+        S2 => "S2",
+        S3 => "S3",
+        S4 => "S4",
+    }
+}
+
+impl Severity {
+    /// Rank the severity from 0 for unspecified or unknown values to 4 for the most urgent ones.
+    /// Equivalent values in the Red Hat, classic, and Mozilla scales share the rank.
+    #[must_use]
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::Urgent | Self::Blocker | Self::S1 => 4,
+            Self::High | Self::Critical | Self::S2 => 3,
+            Self::Medium | Self::Major | Self::Normal | Self::S3 => 2,
+            Self::Low | Self::Minor | Self::Trivial | Self::S4 => 1,
+            Self::Unspecified | Self::Enhancement | Self::Other(_) => 0,
+        }
+    }
+}
+
+field_enum! {
+    /// The priority of a bug. Covers the Red Hat priorities, the default Bugzilla priorities,
+    /// and the classic `P1` to `P5` priorities.
+    Priority {
+        Urgent => "urgent",
+        Highest => "Highest",
+        High => "high",
+        Medium => "medium",
+        Normal => "Normal",
+        Low => "low",
+        Lowest => "Lowest",
+        Unspecified => "unspecified",
+        P1 => "P1",
+        P2 => "P2",
+        P3 => "P3",
+        P4 => "P4",
+        P5 => "P5",
+    }
+}
+
+impl Priority {
+    /// Rank the priority from 0 for unspecified or unknown values to 4 for the most urgent ones.
+    /// Equivalent values in the Red Hat, default Bugzilla, and classic scales share the rank.
+    #[must_use]
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::Urgent | Self::Highest | Self::P1 => 4,
+            Self::High | Self::P2 => 3,
+            Self::Medium | Self::Normal | Self::P3 => 2,
+            Self::Low | Self::Lowest | Self::P4 | Self::P5 => 1,
+            Self::Unspecified | Self::Other(_) => 0,
+        }
+    }
+}
+
+field_enum! {
+    /// The status of a flag, such as `needinfo?`.
+    FlagStatus {
+        Requested => "?",
+        Granted => "+",
+        Denied => "-",
+    }
+}
@@ -0,0 +1,59 @@
+/*
+Copyright 2022 Marek Suchánek <msuchane@redhat.com>
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! This module provides the weights of a composite activity and importance score of a bug.
+//! `Bug::heat` computes the score.
+
+/// The weights that combine the signals of a bug into a single heat score.
+///
+/// The score is a weighted sum:
+///
+/// ```text
+/// heat = cc * (number of CCs)
+///      + comments * (number of comments)
+///      + severity * (severity rank)
+///      + priority * (priority rank)
+///      + recency * 1 / (1 + days since the last change)
+///      + votes * (number of votes)
+/// ```
+///
+/// The severity and priority ranks range from 0 for unspecified values to 4
+/// for the most urgent ones. See `Severity::rank` and `Priority::rank`.
+///
+/// The number of comments is only available if the response includes the `comments` field,
+/// and the number of votes if it includes the `votes` field. Otherwise, they count as 0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeatScorer {
+    pub cc: f64,
+    pub comments: f64,
+    pub severity: f64,
+    pub priority: f64,
+    pub recency: f64,
+    pub votes: f64,
+}
+
+impl Default for HeatScorer {
+    fn default() -> Self {
+        Self {
+            cc: 1.0,
+            comments: 0.5,
+            severity: 5.0,
+            priority: 5.0,
+            recency: 10.0,
+            votes: 2.0,
+        }
+    }
+}
@@ -28,10 +28,18 @@ limitations under the License.
 mod access;
 mod bug_methods;
 mod bug_model;
+mod bug_update;
 mod errors;
+mod field_enums;
+mod heat;
 
-pub use access::{Auth, BzInstance, Pagination};
-pub use bug_model::{Bug, Component, Flag, User, Version};
+pub use access::{Auth, BzInstance, Pagination, SearchIter};
+pub use bug_model::{
+    Attachment, Bug, BugPage, Change, Comment, Component, Flag, HistoryEntry, User, Version,
+};
+pub use bug_update::{BugUpdate, CcUpdate, NewComment};
 pub use errors::BugzillaQueryError;
+pub use field_enums::{FlagStatus, Priority, Resolution, Severity, Status};
+pub use heat::HeatScorer;
 // Re-export JSON Value because it's an integral part of the bug model.
 pub use serde_json::Value;
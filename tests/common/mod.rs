@@ -0,0 +1,188 @@
+//! A minimal HTTP server that replays canned responses,
+//! so that tests can exercise the whole request path without network access.
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A canned HTTP response that the mock server sends back.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    pub status: u16,
+    pub body: String,
+    pub delay: Duration,
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockResponse {
+    /// A successful response with a JSON body.
+    pub fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: body.to_string(),
+            delay: Duration::ZERO,
+            headers: Vec::new(),
+        }
+    }
+
+    /// A response with the specified status code and a raw body.
+    pub fn status(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+            delay: Duration::ZERO,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header to the response.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Wait for the specified time before sending the response.
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// A local server that answers each incoming request with the next canned response.
+/// When it runs out of responses, it keeps repeating the last one.
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            let mut index = 0;
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let response = responses[index.min(responses.len() - 1)].clone();
+                index += 1;
+                respond(stream, &response, &recorded).await;
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// The request targets, such as `/rest/bug?id=1`, in the order that the server received them.
+    pub fn paths(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| {
+                let request_line = request.lines().next().unwrap_or_default();
+                request_line
+                    .split(' ')
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// The complete raw requests, including headers and bodies.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Read a single request from the stream, record it, and send the response.
+async fn respond(mut stream: TcpStream, response: &MockResponse, recorded: &Mutex<Vec<String>>) {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+
+    // Read until the end of the headers.
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    // Read the body, if the request has one.
+    let header_end = buffer
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map_or(buffer.len(), |position| position + 4);
+    let content_length = String::from_utf8_lossy(&buffer[..header_end])
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    // Record the request before responding, so that it's visible as soon as the client finishes.
+    let request = String::from_utf8_lossy(&buffer).to_string();
+    recorded.lock().unwrap().push(request);
+
+    tokio::time::sleep(response.delay).await;
+
+    let headers: String = response
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}\r\n"))
+        .collect();
+    let reply = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{headers}Connection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    );
+    // The client might have given up on the response already.
+    stream.write_all(reply.as_bytes()).await.ok();
+    stream.shutdown().await.ok();
+}
+
+/// A local URL where no server is listening.
+pub async fn unused_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    url
+}
+
+/// The bug fixture, with its ID replaced.
+pub fn bug(id: i32) -> Value {
+    let mut bug: Value = serde_json::from_str(include_str!("../fixtures/bug.json")).unwrap();
+    bug["id"] = json!(id);
+    bug
+}
+
+/// A Bugzilla response that lists the specified bugs.
+pub fn bugs_response(bugs: Vec<Value>) -> Value {
+    let total = bugs.len();
+    json!({
+        "bugs": bugs,
+        "offset": 0,
+        "limit": "20",
+        "total_matches": total,
+    })
+}
@@ -1,3 +1,6 @@
+// Keep the existing tests as they are, rather than rewriting them for the lints.
+#![allow(clippy::bool_assert_comparison, clippy::single_component_path_imports)]
+
 use serde_json::Value;
 use tokio;
 
@@ -32,7 +35,22 @@ async fn access_missing_bug() {
     let instance = rh_bugzilla();
     let bug = instance.bug("111111111111111111");
 
-    assert!(matches!(bug.await.unwrap_err(), BugzillaQueryError::NoBugs));
+    assert!(matches!(
+        bug.await.unwrap_err(),
+        BugzillaQueryError::NotFound(id) if id == "111111111111111111"
+    ));
+}
+
+/// Try accessing a bug with an invalid API key.
+#[tokio::test]
+async fn access_with_invalid_key() {
+    let instance = rh_bugzilla().authenticate(Auth::ApiKey("invalid".to_string()));
+    let bug = instance.bug("1906883");
+
+    assert!(matches!(
+        bug.await.unwrap_err(),
+        BugzillaQueryError::Api { .. }
+    ));
 }
 
 /// Check that the bug fields contain the expected values.
@@ -126,7 +144,8 @@ async fn search_for_bugs() {
 #[tokio::test]
 async fn check_no_bugs() {
     let instance = rh_bugzilla();
-    let bugs = instance.bugs(&[]).await;
+    let ids: &[&str] = &[];
+    let bugs = instance.bugs(ids).await;
 
     assert_eq!(bugs.ok(), Some(vec![]));
 }
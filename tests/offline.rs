@@ -0,0 +1,1565 @@
+//! Tests that don't need a live Bugzilla instance.
+//! They deserialize local fixtures or run against a local mock server.
+
+mod common;
+
+use std::time::Duration;
+
+use bugzilla_query::*;
+use chrono::{NaiveDate, TimeZone, Utc};
+use common::{MockResponse, MockServer};
+use serde_json::json;
+
+/// A common convenience function to get anonymous access to the mock server.
+fn mock_bugzilla(server: &MockServer) -> BzInstance {
+    BzInstance::at(server.url.clone()).unwrap()
+}
+
+/// Check that a bug in a regular response deserializes.
+#[tokio::test]
+async fn mock_access_bug() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1906887),
+    ]))])
+    .await;
+    let bug = mock_bugzilla(&server).bug("1906887").await.unwrap();
+
+    assert_eq!(bug.id, 1906887);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1906887&include_fields=_default"]
+    );
+}
+
+/// Check that an error report in the response body surfaces as an API error.
+#[tokio::test]
+async fn api_error() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "error": true,
+        "code": 102,
+        "message": "You are not authorized to access bug #1.",
+        "documentation": "https://bugzilla.readthedocs.org/en/latest/api/",
+    }))])
+    .await;
+    let error = mock_bugzilla(&server).bug("1").await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        BugzillaQueryError::Api { code: 102, ref message } if message.contains("not authorized")
+    ));
+}
+
+/// Check that an error report about a restricted bug in a successful response
+/// is recognized as denied access.
+#[tokio::test]
+async fn access_denied() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "error": true,
+        "code": 102,
+        "message": "You are not authorized to access bug #2.",
+    }))])
+    .await;
+    let error = mock_bugzilla(&server).bugs(&["1", "2"]).await.unwrap_err();
+
+    assert_eq!(error.code(), Some(102));
+    assert!(error.is_access_denied());
+}
+
+/// Check that an empty response for a single bug reports the requested ID.
+#[tokio::test]
+async fn mock_missing_bug() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![]))]).await;
+    let error = mock_bugzilla(&server).bug("42").await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::NotFound(id) if id == "42"));
+}
+
+/// Check that an error report with an error HTTP status still surfaces as an API error.
+#[tokio::test]
+async fn api_error_with_status() {
+    let body = json!({
+        "error": true,
+        "code": 306,
+        "message": "The API key you specified is invalid.",
+    });
+    let server = MockServer::start(vec![MockResponse::status(401, &body.to_string())]).await;
+    let error = mock_bugzilla(&server).bugs(&["1", "2"]).await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::Api { code: 306, .. }));
+}
+
+/// Check that a failed request without an error report surfaces as a request error,
+/// rather than as a deserialization error.
+#[tokio::test]
+async fn http_error_without_report() {
+    let server =
+        MockServer::start(vec![MockResponse::status(502, "<html>Bad Gateway</html>")]).await;
+    let error = mock_bugzilla(&server).bug("1").await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::Request(e) if e.is_status()));
+}
+
+/// Check that the time fields deserialize from the Bugzilla format into `chrono` types.
+#[test]
+fn deserialize_times() {
+    let mut fixture = common::bug(1);
+    fixture["deadline"] = json!("2021-01-15");
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(
+        bug.creation_time,
+        Utc.with_ymd_and_hms(2020, 12, 11, 14, 24, 35).unwrap()
+    );
+    assert_eq!(
+        bug.last_change_time,
+        Utc.with_ymd_and_hms(2021, 6, 15, 12, 3, 57).unwrap()
+    );
+    assert_eq!(bug.deadline, NaiveDate::from_ymd_opt(2021, 1, 15));
+}
+
+/// Check that a missing deadline deserializes as `None`.
+#[test]
+fn deserialize_no_deadline() {
+    let bug: Bug = serde_json::from_value(common::bug(1)).unwrap();
+
+    assert_eq!(bug.deadline, None);
+}
+
+/// Check that an empty deadline deserializes as `None`.
+#[test]
+fn deserialize_empty_deadline() {
+    let mut fixture = common::bug(1);
+    fixture["deadline"] = json!("");
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(bug.deadline, None);
+}
+
+/// Check that an invalid deadline fails to deserialize, rather than silently disappearing.
+#[test]
+fn deserialize_invalid_deadline() {
+    let mut fixture = common::bug(1);
+    fixture["deadline"] = json!("next week");
+
+    assert!(serde_json::from_value::<Bug>(fixture).is_err());
+}
+
+/// Check that a flag deserializes when the setter and requestee are login names.
+#[test]
+fn deserialize_flag_requestee_string() {
+    let flag: Flag =
+        serde_json::from_str(include_str!("fixtures/flag_requestee_string.json")).unwrap();
+
+    assert_eq!(flag.setter, "msuchane@redhat.com");
+    assert_eq!(flag.requestee.as_deref(), Some("jdoe@redhat.com"));
+}
+
+/// Check that a flag deserializes when the setter and requestee are user objects.
+#[test]
+fn deserialize_flag_requestee_object() {
+    let flag: Flag =
+        serde_json::from_str(include_str!("fixtures/flag_requestee_object.json")).unwrap();
+
+    assert_eq!(flag.setter, "msuchane@redhat.com");
+    assert_eq!(flag.requestee.as_deref(), Some("jdoe@redhat.com"));
+}
+
+/// Check that a flag without a requestee deserializes.
+#[test]
+fn deserialize_flag_without_requestee() {
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/flag_requestee_string.json")).unwrap();
+    fixture.as_object_mut().unwrap().remove("requestee");
+    let flag: Flag = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(flag.requestee, None);
+}
+
+/// Check that the flag status deserializes into the enum, and keeps unknown values.
+#[test]
+fn deserialize_flag_status() {
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/flag_requestee_string.json")).unwrap();
+    let flag: Flag = serde_json::from_value(fixture.clone()).unwrap();
+    assert_eq!(flag.status, FlagStatus::Requested);
+    assert_eq!(flag.to_string(), "needinfo: ?");
+
+    fixture["status"] = json!("+");
+    let flag: Flag = serde_json::from_value(fixture.clone()).unwrap();
+    assert_eq!(flag.status, FlagStatus::Granted);
+
+    fixture["status"] = json!("X");
+    let flag: Flag = serde_json::from_value(fixture.clone()).unwrap();
+    assert_eq!(flag.status, FlagStatus::Other("X".to_string()));
+
+    fixture["status"] = json!(1);
+    let flag: Flag = serde_json::from_value(fixture).unwrap();
+    assert_eq!(flag.status, FlagStatus::Other("1".to_string()));
+}
+
+/// Check that the comments in a bug deserialize from the nested response.
+#[tokio::test]
+async fn mock_comments() {
+    let fixture = serde_json::from_str(include_str!("fixtures/comments.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let comments = mock_bugzilla(&server).comments("1906887").await.unwrap();
+
+    assert_eq!(server.paths(), vec!["/rest/bug/1906887/comment"]);
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].count, 0);
+    assert_eq!(comments[0].bug_id, 1906887);
+    assert_eq!(comments[1].text, "An update.");
+    assert_eq!(comments[1].extra["creator_id"], 380085);
+}
+
+/// Check that the first comment is recognized as the description of the bug.
+#[tokio::test]
+async fn mock_description() {
+    let fixture = serde_json::from_str(include_str!("fixtures/comments.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let comments = mock_bugzilla(&server).comments("1906887").await.unwrap();
+    let description = comments.iter().find(|comment| comment.is_description());
+
+    assert_eq!(
+        description.map(|comment| comment.text.as_str()),
+        Some("Description of problem:\n\nThis is a testing bug.")
+    );
+    assert!(!comments[1].is_description());
+}
+
+/// Check the heat score of a bug with the default weights.
+#[test]
+fn heat_score() {
+    let mut fixture = common::bug(1);
+    fixture["cc"] = json!(["a@example.com", "b@example.com"]);
+    fixture["votes"] = json!(3);
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    // One day after the last change in the fixture.
+    let now = Utc.with_ymd_and_hms(2021, 6, 16, 12, 3, 57).unwrap();
+    let heat = bug.heat_at(&HeatScorer::default(), now);
+
+    // CCs: 2 * 1.0, severity: 2 * 5.0, priority: 2 * 5.0, recency: 10.0 / 2, votes: 3 * 2.0
+    assert!((heat - 33.0).abs() < f64::EPSILON);
+}
+
+/// Check that equivalent severities and priorities from different scales share the rank.
+#[test]
+fn field_enum_ranks() {
+    assert_eq!(Severity::from("urgent").rank(), Severity::S1.rank());
+    assert_eq!(Severity::Blocker.rank(), 4);
+    assert_eq!(Severity::from("Medium").rank(), 2);
+    assert_eq!(Severity::Other("custom".to_string()).rank(), 0);
+    assert_eq!(Priority::P1.rank(), 4);
+    assert_eq!(Priority::from("low").rank(), Priority::P5.rank());
+    assert_eq!(Priority::Unspecified.rank(), 0);
+}
+
+/// Check that bug IDs as `String`s produce the same request as `&str`s.
+#[tokio::test]
+async fn mock_string_ids() {
+    let response = common::bugs_response(vec![common::bug(1), common::bug(2)]);
+    let server = MockServer::start(vec![MockResponse::ok(response)]).await;
+    let instance = mock_bugzilla(&server);
+
+    let str_ids = ["1", "2"];
+    let string_ids = vec!["1".to_string(), "2".to_string()];
+    instance.bugs(&str_ids).await.unwrap();
+    instance.bugs(&string_ids).await.unwrap();
+
+    let paths = server.paths();
+    assert_eq!(paths[0], "/rest/bug?id=1,2&include_fields=_default");
+    assert_eq!(paths[0], paths[1]);
+}
+
+/// Check that the attachments in a bug deserialize from the nested response.
+#[tokio::test]
+async fn mock_attachments() {
+    let fixture = serde_json::from_str(include_str!("fixtures/attachments.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let attachments = mock_bugzilla(&server).attachments("1906887").await.unwrap();
+
+    assert_eq!(server.paths(), vec!["/rest/bug/1906887/attachment"]);
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].file_name, "fix.patch");
+    assert!(attachments[0].is_patch);
+    assert_eq!(attachments[0].data.as_deref(), Some("SGVsbG8gd29ybGQK"));
+}
+
+/// Check that attachment metadata excludes the content of the files.
+#[tokio::test]
+async fn mock_attachment_metadata() {
+    let mut fixture: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/attachments.json")).unwrap();
+    fixture["bugs"]["1906887"][0]
+        .as_object_mut()
+        .unwrap()
+        .remove("data");
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let attachments = mock_bugzilla(&server)
+        .attachment_metadata("1906887")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug/1906887/attachment?exclude_fields=data"]
+    );
+    assert_eq!(attachments[0].size, 12);
+    assert_eq!(attachments[0].data, None);
+}
+
+/// Check that a server error on the primary host falls back to a mirror.
+#[tokio::test]
+async fn mirror_after_server_error() {
+    let primary = MockServer::start(vec![MockResponse::status(503, "Unavailable")]).await;
+    let mirror = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = BzInstance::with_mirrors(primary.url.clone(), vec![mirror.url.clone()]).unwrap();
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(primary.paths().len(), 1);
+    assert_eq!(mirror.paths().len(), 1);
+}
+
+/// Check that a connection failure on the primary host falls back to a mirror.
+#[tokio::test]
+async fn mirror_after_connection_failure() {
+    let unreachable = common::unused_url().await;
+    let mirror = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = BzInstance::with_mirrors(unreachable, vec![mirror.url.clone()]).unwrap();
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+}
+
+/// Check that the login only goes to the primary host, and that reads
+/// that fall back to a mirror use the token from the primary host.
+#[tokio::test]
+async fn mirror_login() {
+    let primary = MockServer::start(vec![
+        MockResponse::ok(json!({ "id": 380085, "token": "380085-abcdef" })),
+        MockResponse::status(503, "Service Unavailable"),
+    ])
+    .await;
+    let mirror = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = BzInstance::with_mirrors(primary.url.clone(), vec![mirror.url.clone()])
+        .unwrap()
+        .authenticate(Auth::Login {
+            user: "msuchane@redhat.com".to_string(),
+            password: "password".to_string(),
+        });
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(
+        primary.paths(),
+        vec!["/rest/login", "/rest/bug?id=1&include_fields=_default"]
+    );
+    assert_eq!(
+        mirror.paths(),
+        vec!["/rest/bug?id=1&include_fields=_default"]
+    );
+    assert!(mirror.requests()[0]
+        .to_lowercase()
+        .contains("x-bugzilla-token: 380085-abcdef\r\n"));
+}
+
+/// Check that the login doesn't fall back to a mirror if the primary host is down.
+#[tokio::test]
+async fn no_mirror_login() {
+    let unreachable = common::unused_url().await;
+    let mirror = MockServer::start(vec![MockResponse::ok(
+        json!({ "id": 380085, "token": "380085-abcdef" }),
+    )])
+    .await;
+    let instance = BzInstance::with_mirrors(unreachable, vec![mirror.url.clone()])
+        .unwrap()
+        .authenticate(Auth::Login {
+            user: "msuchane@redhat.com".to_string(),
+            password: "password".to_string(),
+        });
+
+    assert!(matches!(
+        instance.bug("1").await.unwrap_err(),
+        BugzillaQueryError::Request(_)
+    ));
+    assert!(mirror.paths().is_empty());
+}
+
+/// Check that a client error on the primary host doesn't fall back to a mirror.
+#[tokio::test]
+async fn no_mirror_after_client_error() {
+    let primary = MockServer::start(vec![MockResponse::status(404, "Not Found")]).await;
+    let mirror = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = BzInstance::with_mirrors(primary.url.clone(), vec![mirror.url.clone()]).unwrap();
+
+    assert!(instance.bug("1").await.is_err());
+    assert!(mirror.paths().is_empty());
+}
+
+/// Check that all pages of bugs are downloaded until `total_matches` is reached.
+#[tokio::test]
+async fn mock_bugs_all() {
+    let page = |ids: &[i32], total: usize| {
+        json!({
+            "bugs": ids.iter().map(|id| common::bug(*id)).collect::<Vec<_>>(),
+            "total_matches": total,
+        })
+    };
+    let server = MockServer::start(vec![
+        MockResponse::ok(page(&[1, 2], 5)),
+        MockResponse::ok(page(&[3, 4], 5)),
+        MockResponse::ok(page(&[5], 5)),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).paginate(Pagination::Limit(2));
+    let bugs = instance.bugs_all(&["1", "2", "3", "4", "5"]).await.unwrap();
+
+    let ids: Vec<i32> = bugs.iter().map(|bug| bug.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/bug?id=1,2,3,4,5&include_fields=_default&limit=2&offset=0",
+            "/rest/bug?id=1,2,3,4,5&include_fields=_default&limit=2&offset=2",
+            "/rest/bug?id=1,2,3,4,5&include_fields=_default&limit=2&offset=4",
+        ]
+    );
+}
+
+/// Check that pagination stops at a short page if the response doesn't report `total_matches`.
+#[tokio::test]
+async fn mock_search_all_short_page() {
+    let page =
+        |ids: &[i32]| json!({ "bugs": ids.iter().map(|id| common::bug(*id)).collect::<Vec<_>>() });
+    let server = MockServer::start(vec![
+        MockResponse::ok(page(&[1, 2])),
+        MockResponse::ok(page(&[3])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).paginate(Pagination::Limit(2));
+    let bugs = instance.search_all("product=Fedora").await.unwrap();
+
+    assert_eq!(bugs.len(), 3);
+    assert_eq!(server.paths().len(), 2);
+}
+
+/// Check that `bugs_all` accepts bug IDs as `String`s, such as IDs loaded from a configuration.
+#[tokio::test]
+async fn mock_bugs_all_string_ids() {
+    let response = common::bugs_response(vec![common::bug(1), common::bug(2)]);
+    let server = MockServer::start(vec![MockResponse::ok(response)]).await;
+    let ids: Vec<String> = vec!["1".to_string(), "2".to_string()];
+    let bugs = mock_bugzilla(&server).bugs_all(&ids).await.unwrap();
+
+    assert_eq!(bugs.len(), 2);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1,2&include_fields=_default&limit=100&offset=0"]
+    );
+}
+
+/// Check that the search iterator walks through all pages of results.
+#[tokio::test]
+async fn mock_search_iter() {
+    let page = |ids: &[i32]| {
+        json!({
+            "bugs": ids.iter().map(|id| common::bug(*id)).collect::<Vec<_>>(),
+            "total_matches": 3,
+        })
+    };
+    let server = MockServer::start(vec![
+        MockResponse::ok(page(&[1, 2])),
+        MockResponse::ok(page(&[3])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).paginate(Pagination::Limit(2));
+
+    let mut iter = instance.search_iter("product=Fedora");
+    let mut ids = Vec::new();
+    while let Some(bug) = iter.next().await {
+        ids.push(bug.unwrap().id);
+    }
+
+    assert_eq!(ids, vec![1, 2, 3]);
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/bug?product=Fedora&include_fields=_default&limit=2&offset=0",
+            "/rest/bug?product=Fedora&include_fields=_default&limit=2&offset=2",
+        ]
+    );
+}
+
+/// Check that the search iterator stops after a failed request.
+#[tokio::test]
+async fn mock_search_iter_error() {
+    let server = MockServer::start(vec![MockResponse::status(404, "Not Found")]).await;
+    let instance = mock_bugzilla(&server);
+
+    let mut iter = instance.search_iter("product=Fedora");
+
+    assert!(matches!(iter.next().await, Some(Err(_))));
+    assert!(iter.next().await.is_none());
+}
+
+/// Check that server errors are retried until the request succeeds.
+#[tokio::test]
+async fn retry_server_errors() {
+    let server = MockServer::start(vec![
+        MockResponse::status(503, "Unavailable"),
+        MockResponse::status(500, "Internal Server Error"),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(2)
+        .retry_delay(Duration::from_millis(1));
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(server.paths().len(), 3);
+}
+
+/// Check that the request fails after it runs out of retries.
+#[tokio::test]
+async fn retry_limit() {
+    let server = MockServer::start(vec![MockResponse::status(503, "Unavailable")]).await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(1)
+        .retry_delay(Duration::from_millis(1));
+
+    assert!(instance.bug("1").await.is_err());
+    assert_eq!(server.paths().len(), 2);
+}
+
+/// Check that rate limiting is retried after the delay in the `Retry-After` header.
+#[tokio::test]
+async fn retry_rate_limiting() {
+    let server = MockServer::start(vec![
+        MockResponse::status(429, "Too Many Requests").header("Retry-After", "1"),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(1)
+        .retry_delay(Duration::from_millis(1));
+    let start = std::time::Instant::now();
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(server.paths().len(), 2);
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+/// Check that a huge `Retry-After` falls back to the backoff, rather than stalling the request.
+#[tokio::test]
+async fn retry_after_capped() {
+    let server = MockServer::start(vec![
+        MockResponse::status(429, "Too Many Requests").header("Retry-After", "86400"),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(1)
+        .retry_delay(Duration::from_millis(1));
+    let bug = tokio::time::timeout(Duration::from_secs(5), instance.bug("1"))
+        .await
+        .expect("The retry waited for the whole Retry-After delay.")
+        .unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(server.paths().len(), 2);
+}
+
+/// Check that a response that fails to deserialize isn't retried.
+#[tokio::test]
+async fn no_retry_deserialization_errors() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({ "unexpected": true }))]).await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(3)
+        .retry_delay(Duration::from_millis(1));
+    let error = instance.bug("1").await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::Deserialization(_)));
+    assert_eq!(server.paths().len(), 1);
+}
+
+/// Check that client errors aren't retried.
+#[tokio::test]
+async fn no_retry_client_errors() {
+    let server = MockServer::start(vec![MockResponse::status(404, "Not Found")]).await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(3)
+        .retry_delay(Duration::from_millis(1));
+
+    assert!(instance.bug("1").await.is_err());
+    assert_eq!(server.paths().len(), 1);
+}
+
+/// Check that a page of results requests both the limit and the offset.
+#[tokio::test]
+async fn mock_page() {
+    let response = common::bugs_response(vec![common::bug(3), common::bug(4)]);
+    let server = MockServer::start(vec![MockResponse::ok(response)]).await;
+    let instance = mock_bugzilla(&server).paginate(Pagination::Page {
+        limit: 2,
+        offset: 2,
+    });
+    let bugs = instance.search("product=Fedora").await.unwrap();
+
+    assert_eq!(bugs.len(), 2);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?product=Fedora&include_fields=_default&limit=2&offset=2"]
+    );
+}
+
+/// Check the typed accessors of the status, resolution, severity, and priority.
+#[test]
+fn field_enums() {
+    let mut fixture = common::bug(1);
+    fixture["priority"] = json!("P2");
+    fixture["severity"] = json!("S1");
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(bug.status_enum(), Status::Closed);
+    assert_eq!(bug.resolution_enum(), Resolution::CurrentRelease);
+    assert_eq!(bug.severity_enum(), Severity::S1);
+    assert_eq!(bug.priority_enum(), Priority::P2);
+}
+
+/// Check that unknown values survive in the `Other` variant,
+/// and that known values match regardless of case.
+#[test]
+fn field_enums_other() {
+    assert_eq!(
+        Status::from("NEEDINFO"),
+        Status::Other("NEEDINFO".to_string())
+    );
+    assert_eq!(Status::from("NEEDINFO").to_string(), "NEEDINFO");
+    assert_eq!(Severity::from("Medium"), Severity::Medium);
+    assert_eq!(Resolution::from(""), Resolution::Unresolved);
+    assert_eq!(Status::OnQa.as_str(), "ON_QA");
+}
+
+/// Check that a request that exceeds the time limit fails with a timeout.
+#[tokio::test]
+async fn timeout() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))
+    .delayed(Duration::from_secs(5))])
+    .await;
+    let instance = mock_bugzilla(&server).timeout(Duration::from_millis(100));
+    let error = instance.bug("1").await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::Timeout(_)));
+}
+
+/// Check that a bug can be requested by its alias, and that the alias deserializes.
+#[tokio::test]
+async fn mock_bug_by_alias() {
+    let mut fixture = common::bug(1);
+    fixture["alias"] = json!(["CVE-2022-1234"]);
+    let server =
+        MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![fixture]))]).await;
+    let bug = mock_bugzilla(&server)
+        .bug_by_alias("CVE-2022-1234")
+        .await
+        .unwrap();
+
+    assert_eq!(bug.alias, vec!["CVE-2022-1234"]);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?alias=CVE-2022-1234&include_fields=_default"]
+    );
+}
+
+/// Check that a missing alias reports the requested alias.
+#[tokio::test]
+async fn mock_missing_alias() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![]))]).await;
+    let error = mock_bugzilla(&server)
+        .bug_by_alias("no-such-alias")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::NotFound(alias) if alias == "no-such-alias"));
+}
+
+/// Check the accessors of custom fields.
+#[test]
+fn custom_fields() {
+    let mut fixture = common::bug(1);
+    fixture["cf_fixed_in"] = json!("bugzilla_query-1.0.2");
+    fixture["cf_story_points"] = json!(3);
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(
+        bug.custom_field("cf_fixed_in"),
+        Some(&json!("bugzilla_query-1.0.2"))
+    );
+    assert_eq!(bug.custom_field("cf_missing"), None);
+
+    let points: Option<Result<u32, _>> = bug.custom_field_as("cf_story_points");
+    assert_eq!(points.unwrap().unwrap(), 3);
+    let wrong_type: Option<Result<u32, _>> = bug.custom_field_as("cf_fixed_in");
+    assert!(wrong_type.unwrap().is_err());
+
+    let mut names: Vec<&str> = bug.custom_fields().collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        vec!["cf_fixed_in", "cf_release_notes", "cf_story_points"]
+    );
+}
+
+/// Check that the login token authenticates the following requests.
+#[tokio::test]
+async fn login() {
+    let server = MockServer::start(vec![
+        MockResponse::ok(json!({ "id": 380085, "token": "380085-abcdef" })),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).authenticate(Auth::Login {
+        user: "msuchane@redhat.com".to_string(),
+        password: "p@ss word".to_string(),
+    });
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/login", "/rest/bug?id=1&include_fields=_default"]
+    );
+
+    let requests = server.requests();
+    let login = requests[0].to_lowercase();
+    assert!(login.contains("x-bugzilla-login: msuchane@redhat.com\r\n"));
+    assert!(login.contains("x-bugzilla-password: p@ss word\r\n"));
+    assert!(requests[1]
+        .to_lowercase()
+        .contains("x-bugzilla-token: 380085-abcdef\r\n"));
+}
+
+/// Check that the login happens only once, and later requests reuse the token.
+#[tokio::test]
+async fn login_once() {
+    let server = MockServer::start(vec![
+        MockResponse::ok(json!({ "id": 380085, "token": "380085-abcdef" })),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).authenticate(Auth::Login {
+        user: "msuchane@redhat.com".to_string(),
+        password: "password".to_string(),
+    });
+    instance.bug("1").await.unwrap();
+    instance.bug("1").await.unwrap();
+    instance.search("product=Fedora").await.unwrap();
+
+    let paths = server.paths();
+    assert_eq!(paths.len(), 4);
+    assert_eq!(
+        paths
+            .iter()
+            .filter(|path| path.starts_with("/rest/login"))
+            .count(),
+        1
+    );
+    assert!(server.requests()[1..].iter().all(|request| request
+        .to_lowercase()
+        .contains("x-bugzilla-token: 380085-abcdef\r\n")));
+}
+
+/// Check that a transient failure of the login is retried, like other requests.
+#[tokio::test]
+async fn retry_login() {
+    let server = MockServer::start(vec![
+        MockResponse::status(503, "Service Unavailable"),
+        MockResponse::ok(json!({ "id": 380085, "token": "380085-abcdef" })),
+        MockResponse::ok(common::bugs_response(vec![common::bug(1)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .with_retries(1)
+        .retry_delay(Duration::from_millis(1))
+        .authenticate(Auth::Login {
+            user: "msuchane@redhat.com".to_string(),
+            password: "password".to_string(),
+        });
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(bug.id, 1);
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/login",
+            "/rest/login",
+            "/rest/bug?id=1&include_fields=_default"
+        ]
+    );
+}
+
+/// Check that rejected credentials fail with a login error, rather than continuing anonymously.
+#[tokio::test]
+async fn login_rejected() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "error": true,
+        "code": 300,
+        "message": "The username or password you entered is not valid.",
+    }))])
+    .await;
+    let instance = mock_bugzilla(&server).authenticate(Auth::Login {
+        user: "msuchane@redhat.com".to_string(),
+        password: "wrong".to_string(),
+    });
+    let error = instance.bug("1").await.unwrap_err();
+
+    assert!(matches!(error, BugzillaQueryError::Login(message) if message.contains("not valid")));
+    assert_eq!(server.paths().len(), 1);
+}
+
+/// Check that the extra headers and the authentication header are both sent.
+#[tokio::test]
+async fn extra_headers() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .user_agent("first-agent")
+        .user_agent("release-tool/1.0")
+        .header("X-Forwarded-For", "10.0.0.1")
+        .authenticate(Auth::ApiKey("secret".to_string()));
+    instance.bug("1").await.unwrap();
+
+    let request = server.requests()[0].to_lowercase();
+    assert!(request.contains("user-agent: release-tool/1.0\r\n"));
+    assert!(!request.contains("first-agent"));
+    assert!(request.contains("x-forwarded-for: 10.0.0.1\r\n"));
+    assert!(request.contains("authorization: bearer secret\r\n"));
+}
+
+/// Check that the time limit also applies to the login request and to other endpoints.
+#[tokio::test]
+async fn timeout_everywhere() {
+    let server = MockServer::start(vec![
+        MockResponse::ok(json!({})).delayed(Duration::from_secs(5))
+    ])
+    .await;
+    let anonymous = mock_bugzilla(&server).timeout(Duration::from_millis(100));
+    let logged_in = mock_bugzilla(&server)
+        .timeout(Duration::from_millis(100))
+        .authenticate(Auth::Login {
+            user: "msuchane@redhat.com".to_string(),
+            password: "s3cret-pa55word".to_string(),
+        });
+
+    assert!(matches!(
+        anonymous.comments("1").await.unwrap_err(),
+        BugzillaQueryError::Timeout(_)
+    ));
+    let error = logged_in.bug("1").await.unwrap_err();
+    assert!(matches!(error, BugzillaQueryError::Timeout(_)));
+
+    // The error must not leak the password, neither in the message nor in its sources.
+    let mut messages = vec![error.to_string(), format!("{error:?}")];
+    let mut source = std::error::Error::source(&error);
+    while let Some(cause) = source {
+        messages.push(cause.to_string());
+        source = cause.source();
+    }
+    assert!(messages
+        .iter()
+        .all(|message| !message.contains("s3cret-pa55word")));
+}
+
+/// Check that a long list of IDs is split into several requests, and that the bugs keep their order.
+#[tokio::test]
+async fn chunked_ids() {
+    let server = MockServer::start(vec![
+        MockResponse::ok(common::bugs_response(vec![common::bug(1), common::bug(2)])),
+        MockResponse::ok(common::bugs_response(vec![common::bug(3), common::bug(4)])),
+        MockResponse::ok(common::bugs_response(vec![common::bug(5)])),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).max_ids_per_request(2);
+    let bugs = instance.bugs(&["1", "2", "3", "4", "5"]).await.unwrap();
+
+    let ids: Vec<i32> = bugs.iter().map(|bug| bug.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/bug?id=1,2&include_fields=_default",
+            "/rest/bug?id=3,4&include_fields=_default",
+            "/rest/bug?id=5&include_fields=_default",
+        ]
+    );
+}
+
+/// Check that an exact multiple of the chunk size doesn't produce an extra, empty request.
+#[tokio::test]
+async fn chunked_ids_exact_multiple() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+        common::bug(2),
+    ]))])
+    .await;
+    let instance = mock_bugzilla(&server).max_ids_per_request(2);
+    instance.bugs(&["1", "2", "3", "4"]).await.unwrap();
+
+    assert_eq!(server.paths().len(), 2);
+}
+
+/// Check that the raw response keeps everything, even if it doesn't deserialize into bugs.
+#[tokio::test]
+async fn mock_bugs_raw() {
+    let mut fixture = common::bug(1);
+    // A schema change that would break the `Bug` struct.
+    fixture["is_open"] = json!("yes");
+    let response = common::bugs_response(vec![fixture]);
+    let server = MockServer::start(vec![MockResponse::ok(response.clone())]).await;
+    let instance = mock_bugzilla(&server);
+    let raw = instance.bugs_raw(&["1"]).await.unwrap();
+
+    assert_eq!(raw, response);
+    assert!(instance.bugs(&["1"]).await.is_err());
+}
+
+/// Check that the raw response still recognizes error reports.
+#[tokio::test]
+async fn mock_bugs_raw_error() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "error": true,
+        "code": 102,
+        "message": "You are not authorized to access bug #1.",
+    }))])
+    .await;
+    let error = mock_bugzilla(&server).bugs_raw(&["1"]).await.unwrap_err();
+
+    assert!(error.is_access_denied());
+}
+
+/// Check that the time tracking fields accept integers, floating-point numbers, and `null`.
+#[test]
+fn deserialize_hours() {
+    let mut fixture = common::bug(1);
+    fixture["estimated_time"] = json!(4);
+    fixture["remaining_time"] = json!(1.5);
+    fixture["actual_time"] = json!(null);
+    fixture["work_time"] = json!(2.0);
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(bug.estimated_time, Some(4));
+    assert_eq!(bug.remaining_time, Some(2));
+    assert_eq!(bug.actual_time, None);
+    assert_eq!(bug.work_time, Some(2));
+}
+
+/// Check that missing time tracking fields deserialize as `None`.
+#[test]
+fn deserialize_missing_hours() {
+    let mut fixture = common::bug(1);
+    fixture.as_object_mut().unwrap().remove("work_time");
+    let bug: Bug = serde_json::from_value(fixture).unwrap();
+
+    assert_eq!(bug.work_time, None);
+}
+
+/// Check that requested custom fields reach the request and come back as custom fields.
+#[tokio::test]
+async fn mock_custom_fields() {
+    let mut fixture = common::bug(1);
+    fixture["cf_fixed_in"] = json!("bugzilla_query-1.0.2");
+    let server =
+        MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![fixture]))]).await;
+    let instance = mock_bugzilla(&server)
+        .include_fields(vec!["_default".to_string(), "cf_fixed_in".to_string()]);
+    let bug = instance.bug("1").await.unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1&include_fields=_default,cf_fixed_in"]
+    );
+    assert_eq!(
+        bug.custom_field_as::<String>("cf_fixed_in")
+            .unwrap()
+            .unwrap(),
+        "bugzilla_query-1.0.2"
+    );
+}
+
+/// Check that the history of a bug deserializes from the nested response.
+#[tokio::test]
+async fn mock_history() {
+    let fixture = serde_json::from_str(include_str!("fixtures/history.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let history = mock_bugzilla(&server).history("1906887").await.unwrap();
+
+    assert_eq!(server.paths(), vec!["/rest/bug/1906887/history"]);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].who, "msuchane@redhat.com");
+    assert_eq!(history[0].changes[0].field_name, "status");
+    assert_eq!(history[0].changes[0].removed, "NEW");
+    assert_eq!(history[0].changes[0].added, "ASSIGNED");
+    assert_eq!(history[0].changes[0].attachment_id, None);
+    assert_eq!(history[1].changes[0].attachment_id, Some(1737673));
+}
+
+/// Check that the history reveals when and how a field changed.
+#[tokio::test]
+async fn mock_history_status_change() {
+    let fixture = serde_json::from_str(include_str!("fixtures/history.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let history = mock_bugzilla(&server).history("1906887").await.unwrap();
+
+    let (when, change) = history
+        .iter()
+        .find_map(|entry| entry.change("status").map(|change| (entry.when, change)))
+        .unwrap();
+
+    assert_eq!(
+        when,
+        Utc.with_ymd_and_hms(2020, 12, 11, 14, 30, 12).unwrap()
+    );
+    assert_eq!(change.added, "ASSIGNED");
+    assert!(history[1].change("status").is_none());
+}
+
+/// Check that the history can be limited to recent changes.
+#[tokio::test]
+async fn mock_history_since() {
+    let fixture = serde_json::from_str(include_str!("fixtures/history.json")).unwrap();
+    let server = MockServer::start(vec![MockResponse::ok(fixture)]).await;
+    let since = Utc.with_ymd_and_hms(2020, 12, 12, 0, 0, 0).unwrap();
+    mock_bugzilla(&server)
+        .history_since("1906887", since)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug/1906887/history?new_since=2020-12-12T00:00:00Z"]
+    );
+}
+
+/// Check that the IDs missing from the response are reported, including those requested by alias.
+#[tokio::test]
+async fn mock_bugs_checked() {
+    let mut aliased = common::bug(3);
+    aliased["alias"] = json!(["CVE-2022-1234"]);
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+        aliased,
+    ]))])
+    .await;
+    let (bugs, missing) = mock_bugzilla(&server)
+        .bugs_checked(&["1", "123", "CVE-2022-1234", "456"])
+        .await
+        .unwrap();
+
+    assert_eq!(bugs.len(), 2);
+    assert_eq!(missing, vec!["123", "456"]);
+}
+
+/// Check that no matching bugs result in all IDs missing, rather than an error.
+#[tokio::test]
+async fn mock_bugs_checked_none() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![]))]).await;
+    let (bugs, missing) = mock_bugzilla(&server)
+        .bugs_checked(&["123", "456"])
+        .await
+        .unwrap();
+
+    assert!(bugs.is_empty());
+    assert_eq!(missing, vec!["123", "456"]);
+}
+
+/// Check that a client configured by the caller sends its own settings, and that the builder methods still apply.
+#[tokio::test]
+async fn mock_with_client() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+
+    let mut default_headers = reqwest::header::HeaderMap::new();
+    default_headers.insert("X-Client", "preconfigured".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(default_headers)
+        .build()
+        .unwrap();
+
+    let instance = BzInstance::with_client(server.url.clone(), client)
+        .unwrap()
+        .authenticate(Auth::ApiKey("key".to_string()))
+        .include_fields(vec!["id".to_string()]);
+    instance.bug("1").await.unwrap();
+
+    let request = server.requests().remove(0).to_lowercase();
+    assert!(request.contains("x-client: preconfigured"));
+    assert!(request.contains("include_fields=id"));
+}
+
+/// Check that the search metadata reaches the caller, so that they can detect truncated results.
+#[tokio::test]
+async fn mock_search_with_meta() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "bugs": [common::bug(1), common::bug(2)],
+        "offset": 0,
+        "limit": "2",
+        "total_matches": 5000,
+    }))])
+    .await;
+    let page = mock_bugzilla(&server)
+        .search_with_meta("product=Fedora")
+        .await
+        .unwrap();
+
+    assert_eq!(page.bugs.len(), 2);
+    assert_eq!(page.total_matches, Some(5000));
+    assert_eq!(page.offset, Some(0));
+    assert_eq!(page.limit, Some(2));
+    assert!(page.has_more());
+}
+
+/// Check that a response without metadata or bugs is still a valid page.
+#[tokio::test]
+async fn mock_bugs_with_meta_empty() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({ "bugs": [] }))]).await;
+    let page = mock_bugzilla(&server)
+        .bugs_with_meta(&["123"])
+        .await
+        .unwrap();
+
+    assert!(page.bugs.is_empty());
+    assert_eq!(page.total_matches, None);
+    assert_eq!(page.offset, None);
+    assert!(!page.has_more());
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=123&include_fields=_default"]
+    );
+}
+
+/// Check that a valid root certificate keeps the instance usable.
+#[tokio::test]
+async fn mock_add_root_certificate() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .add_root_certificate(include_bytes!("fixtures/root_ca.pem"))
+        .unwrap();
+
+    assert_eq!(instance.bug("1").await.unwrap().id, 1);
+}
+
+/// Check that an invalid certificate results in a TLS error.
+#[tokio::test]
+async fn invalid_root_certificate() {
+    let result = BzInstance::at("https://bugzilla.example.com".to_string())
+        .unwrap()
+        .add_root_certificate(b"not a certificate");
+
+    assert!(matches!(result, Err(BugzillaQueryError::Tls(_))));
+}
+
+/// Check that skipping the certificate verification keeps the instance usable.
+#[cfg(feature = "danger-accept-invalid-certs")]
+#[tokio::test]
+async fn mock_danger_accept_invalid_certs() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = mock_bugzilla(&server)
+        .danger_accept_invalid_certs(true)
+        .unwrap();
+
+    assert_eq!(instance.bug("1").await.unwrap().id, 1);
+}
+
+/// Check that a clone with different options works independently of the original.
+#[tokio::test]
+async fn mock_clone_instance() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let original = mock_bugzilla(&server);
+    let limited = original.clone().paginate(Pagination::Limit(5));
+
+    original.search("product=Fedora").await.unwrap();
+    limited.search("product=Fedora").await.unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/bug?product=Fedora&include_fields=_default",
+            "/rest/bug?product=Fedora&include_fields=_default&limit=5",
+        ]
+    );
+}
+
+/// Check that an instance can be shared between threads.
+#[test]
+fn instance_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<BzInstance>();
+}
+
+/// Check that an update sends only the fields that are set, to the bug URL.
+#[tokio::test]
+async fn mock_update_bug() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "bugs": [{ "id": 1, "changes": {} }]
+    }))])
+    .await;
+    let update = BugUpdate::default()
+        .status(Status::Closed)
+        .comment("Fixed in the 1.2 release.")
+        .add_cc("jdoe@redhat.com");
+    mock_bugzilla(&server)
+        .authenticate(Auth::ApiKey("key".to_string()))
+        .update_bug("1", &update)
+        .await
+        .unwrap();
+
+    let request = server.requests().remove(0);
+    assert!(request.starts_with("PUT /rest/bug/1 "));
+    let body = request.split("\r\n\r\n").nth(1).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(body).unwrap(),
+        json!({
+            "status": "CLOSED",
+            "comment": { "body": "Fixed in the 1.2 release.", "is_private": false },
+            "cc": { "add": ["jdoe@redhat.com"] },
+        })
+    );
+}
+
+/// Check that an anonymous instance refuses to update bugs without contacting the server.
+#[tokio::test]
+async fn update_bug_anonymous() {
+    let instance = BzInstance::at(common::unused_url().await).unwrap();
+    let result = instance.update_bug("1", &BugUpdate::default()).await;
+
+    assert!(matches!(result, Err(BugzillaQueryError::AuthRequired)));
+}
+
+/// Check that an error from Bugzilla during an update reaches the caller.
+#[tokio::test]
+async fn mock_update_bug_error() {
+    let server = MockServer::start(vec![MockResponse::status(
+        400,
+        r#"{"error": true, "code": 60, "message": "You must specify a resolution.", "documentation": ""}"#,
+    )])
+    .await;
+    let result = mock_bugzilla(&server)
+        .authenticate(Auth::ApiKey("key".to_string()))
+        .update_bug("1", &BugUpdate::default().status(Status::Closed))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(BugzillaQueryError::Api { code: 60, .. })
+    ));
+}
+
+/// Check that unknown field names still reach Bugzilla, because the check only warns about them.
+#[tokio::test]
+async fn mock_include_unknown_fields() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    mock_bugzilla(&server)
+        .include_fields(vec![
+            "_defualt".to_string(),
+            "cf_fixed_in".to_string(),
+            "flags.name".to_string(),
+        ])
+        .bug("1")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1&include_fields=_defualt,cf_fixed_in,flags.name"]
+    );
+}
+
+/// Check that the current user deserializes, even though the response has no email.
+#[tokio::test]
+async fn mock_whoami() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "id": 42,
+        "name": "msuchane@redhat.com",
+        "real_name": "Marek Suchánek",
+    }))])
+    .await;
+    let user = mock_bugzilla(&server)
+        .authenticate(Auth::ApiKey("key".to_string()))
+        .whoami()
+        .await
+        .unwrap();
+
+    assert_eq!(user.id, 42);
+    assert_eq!(user.name, "msuchane@redhat.com");
+    assert_eq!(user.email, "");
+    assert_eq!(server.paths(), vec!["/rest/whoami"]);
+}
+
+/// Check that Bugzilla refusing an anonymous user results in an API error.
+#[tokio::test]
+async fn mock_whoami_anonymous() {
+    let server = MockServer::start(vec![MockResponse::status(
+        401,
+        r#"{"error": true, "code": 410, "message": "You must log in before using this part of Bugzilla.", "documentation": ""}"#,
+    )])
+    .await;
+    let result = mock_bugzilla(&server).whoami().await;
+
+    assert!(matches!(
+        result,
+        Err(BugzillaQueryError::Api { code: 410, .. })
+    ));
+}
+
+/// Check that a trailing slash in the host doesn't result in a double slash in the request.
+#[tokio::test]
+async fn mock_host_trailing_slash() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    let instance = BzInstance::at(format!("{}/", server.url)).unwrap();
+    instance.bug("1").await.unwrap();
+
+    assert_eq!(instance.host, server.url);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1&include_fields=_default"]
+    );
+}
+
+/// Check that hosts without a supported scheme are rejected up front.
+#[test]
+fn invalid_hosts() {
+    for host in [
+        "bugzilla.redhat.com",
+        "ftp://bugzilla.redhat.com",
+        "https://",
+        "",
+    ] {
+        assert!(
+            matches!(
+                BzInstance::at(host.to_string()),
+                Err(BugzillaQueryError::InvalidHost(_))
+            ),
+            "{host}"
+        );
+    }
+    assert!(matches!(
+        BzInstance::with_mirrors(
+            "https://bugzilla.redhat.com".to_string(),
+            vec!["mirror.example.com".to_string()]
+        ),
+        Err(BugzillaQueryError::InvalidHost(_))
+    ));
+}
+
+/// Check that the last page of results has no more bugs after it.
+#[tokio::test]
+async fn mock_last_page_has_no_more() {
+    let server = MockServer::start(vec![MockResponse::ok(json!({
+        "bugs": [common::bug(5)],
+        "offset": 4,
+        "limit": "2",
+        "total_matches": 5,
+    }))])
+    .await;
+    let page = mock_bugzilla(&server)
+        .paginate(Pagination::Page {
+            limit: 2,
+            offset: 4,
+        })
+        .search_with_meta("product=Fedora")
+        .await
+        .unwrap();
+
+    assert_eq!(page.offset, Some(4));
+    assert!(!page.has_more());
+}
+
+/// Check that the comments of several bugs come from one request and are keyed by the bug ID.
+#[tokio::test]
+async fn mock_comments_for() {
+    let mut response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/comments.json")).unwrap();
+    let mut other = response["bugs"]["1906887"].clone();
+    other["comments"][0]["bug_id"] = json!(1906883);
+    response["bugs"]["1906883"] = other;
+
+    let server = MockServer::start(vec![MockResponse::ok(response)]).await;
+    let comments = mock_bugzilla(&server)
+        .comments_for(&["1906887", "1906883"])
+        .await
+        .unwrap();
+
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[&1906887].len(), 2);
+    assert_eq!(comments[&1906883][0].bug_id, 1906883);
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug/1906887/comment?ids=1906883"]
+    );
+}
+
+/// Check that the comments of many bugs are split into several requests.
+#[tokio::test]
+async fn mock_comments_for_chunks() {
+    let server = MockServer::start(vec![MockResponse::ok(
+        serde_json::from_str(include_str!("fixtures/comments.json")).unwrap(),
+    )])
+    .await;
+    mock_bugzilla(&server)
+        .max_ids_per_request(2)
+        .comments_for(&["1", "2", "3"])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug/1/comment?ids=2", "/rest/bug/3/comment"]
+    );
+}
+
+/// Check that `with_flags` requests the flags once, and that they deserialize.
+#[tokio::test]
+async fn mock_with_flags() {
+    let mut bug = common::bug(1);
+    bug["flags"] = json!([serde_json::from_str::<serde_json::Value>(include_str!(
+        "fixtures/flag_requestee_string.json"
+    ))
+    .unwrap()]);
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![bug]))]).await;
+    let bug = mock_bugzilla(&server)
+        .with_flags()
+        .with_flags()
+        .bug("1")
+        .await
+        .unwrap();
+
+    let flags = bug.flags.as_ref().unwrap();
+    assert_eq!(flags.len(), 1);
+    assert_eq!(flags[0].name, "needinfo");
+    assert_eq!(bug.get_flag("needinfo"), Some("?"));
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1&include_fields=_default,flags"]
+    );
+}
+
+/// Check that `with_flags` keeps the default fields after clearing the included fields.
+#[tokio::test]
+async fn mock_with_flags_no_fields() {
+    let server = MockServer::start(vec![MockResponse::ok(common::bugs_response(vec![
+        common::bug(1),
+    ]))])
+    .await;
+    mock_bugzilla(&server)
+        .include_fields(vec![])
+        .with_flags()
+        .bug("1")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        server.paths(),
+        vec!["/rest/bug?id=1&include_fields=_default,flags"]
+    );
+}
+
+/// Check that a bug without the flags field has no flags, rather than an empty list.
+#[test]
+fn deserialize_bug_without_flags() {
+    let bug: Bug = serde_json::from_value(common::bug(1)).unwrap();
+
+    assert_eq!(bug.flags, None);
+}
+
+/// Check that the next page continues after the current one, with the same page size.
+#[tokio::test]
+async fn mock_next_page() {
+    let server = MockServer::start(vec![
+        MockResponse::ok(json!({
+            "bugs": [common::bug(3), common::bug(4)],
+            "offset": 2,
+            "limit": "2",
+            "total_matches": 5,
+        })),
+        MockResponse::ok(json!({
+            "bugs": [common::bug(5)],
+            "offset": 4,
+            "limit": "2",
+            "total_matches": 5,
+        })),
+    ])
+    .await;
+    let instance = mock_bugzilla(&server).paginate(Pagination::Page {
+        limit: 2,
+        offset: 2,
+    });
+    let page = instance.search_with_meta("product=Fedora").await.unwrap();
+    let next = page.next_page().unwrap();
+    assert_eq!(
+        next,
+        Pagination::Page {
+            limit: 2,
+            offset: 4
+        }
+    );
+
+    let last = instance
+        .paginate(next)
+        .search_with_meta("product=Fedora")
+        .await
+        .unwrap();
+    assert_eq!(last.next_page(), None);
+    assert_eq!(
+        server.paths(),
+        vec![
+            "/rest/bug?product=Fedora&include_fields=_default&limit=2&offset=2",
+            "/rest/bug?product=Fedora&include_fields=_default&limit=2&offset=4",
+        ]
+    );
+}
+
+/// Check that no IDs result in an empty raw response, without contacting the server.
+#[tokio::test]
+async fn bugs_raw_no_ids() {
+    let instance = BzInstance::at(common::unused_url().await).unwrap();
+    let ids: &[&str] = &[];
+
+    assert_eq!(instance.bugs_raw(ids).await.unwrap(), json!({ "bugs": [] }));
+}